@@ -0,0 +1,66 @@
+//! Compares the protocol `State` with `CompactState` for the operations a search performs per node,
+//! and measures move generation and application of the rules engine on a generated board.
+//!
+//! Run with `cargo bench --bench compact`.
+
+use std::hint::black_box;
+use std::time::Instant;
+
+use socha_rust_core::api::compact::CompactState;
+use socha_rust_core::api::protocol::{Board, State};
+
+const ITERATIONS: u32 = 100_000;
+
+const NOTATION: &str = "1 2 17 UR R@0,0:wwwww|wiwww|pUL1swwg|wwwww/DR@2,4:wwwww|pR0wwww|wwwww|wwwww/R@6,4:wwwww|wwiww|wwwww|wwswpL1/R@10,4:wwwww|wwwww|wwwww|ggggg 1R@-1,-1:1,6,0,1,0 2DL@3,4:4,0,2,3,-12";
+
+fn bench(name: &str, mut f: impl FnMut()) {
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        f();
+    }
+    let elapsed = start.elapsed();
+    println!("{:<32} {:>10.1} ns/iter", name, elapsed.as_nanos() as f64 / ITERATIONS as f64);
+}
+
+fn main() {
+    let state = State::from_notation(NOTATION).unwrap();
+    let compact = CompactState::try_from(&state).unwrap();
+    let target = state.ship[1].position.clone();
+
+    bench("State::clone", || {
+        black_box(black_box(&state).clone());
+    });
+    bench("CompactState copy", || {
+        black_box(*black_box(&compact));
+    });
+    bench("State -> CompactState", || {
+        black_box(CompactState::try_from(black_box(&state)).unwrap());
+    });
+    bench("CompactState -> State", || {
+        black_box(State::from(black_box(&compact)));
+    });
+    bench("Board::field_at", || {
+        black_box(black_box(&state).board.field_at(&target));
+    });
+    bench("CompactState::field_id + field", || {
+        let compact = black_box(&compact);
+        black_box(compact.field_id(&target).and_then(|id| compact.field(id)));
+    });
+    bench("State::zobrist_hash", || {
+        black_box(black_box(&state).zobrist_hash());
+    });
+
+    let mut start = State::start(Board::generate(1));
+    let first = start.legal_moves()[0].clone();
+    bench("State::legal_moves", || {
+        black_box(black_box(&start).legal_moves());
+    });
+    bench("State::make_move + unmake_move", || {
+        let undo = black_box(&mut start).make_move(&first).unwrap();
+        start.unmake_move(undo);
+    });
+    bench("State::clone + make_move", || {
+        let mut next = black_box(&start).clone();
+        black_box(next.make_move(&first).unwrap());
+    });
+}
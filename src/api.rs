@@ -1,6 +1,33 @@
+pub mod board;
+pub mod board_test;
+pub mod compact;
+pub mod compact_test;
+pub mod display;
+pub mod display_test;
 pub mod game_client;
 pub mod game_client_test;
+pub mod generator;
+pub mod generator_test;
+#[cfg(test)]
+pub mod mock_server;
+pub mod notation;
+pub mod notation_test;
 pub mod protocol;
 pub mod protocol_test;
+pub mod recorder;
+pub mod recorder_test;
+pub mod replay;
+pub mod replay_test;
+pub mod result;
+pub mod result_test;
+pub mod rules;
+pub mod rules_test;
+pub mod serde_test;
+pub mod server;
+pub mod server_test;
+pub mod svg;
+pub mod svg_test;
 pub mod xml_buffer;
-pub mod xml_buffer_test;
\ No newline at end of file
+pub mod xml_buffer_test;
+pub mod zobrist;
+pub mod zobrist_test;
\ No newline at end of file
@@ -0,0 +1,179 @@
+use std::ops::{Add, Mul, Sub};
+
+use crate::api::protocol::{Board, Coordinate, CubeDirection, Field, Segment, Team};
+
+/// Number of columns (field arrays) in a segment.
+pub const SEGMENT_COLUMNS: usize = 4;
+
+/// Number of fields in each column of a segment.
+pub const SEGMENT_ROWS: usize = 5;
+
+impl Coordinate {
+    pub fn new(q: i32, r: i32) -> Self {
+        Coordinate { q, r, s: -q - r }
+    }
+
+    /// Rotates the coordinate around the origin by `turns` steps of 60 degrees clockwise.
+    pub fn rotated_by(&self, turns: i32) -> Self {
+        let mut rotated = self.clone();
+        for _ in 0..turns.rem_euclid(6) {
+            rotated = Coordinate { q: -rotated.r, r: -rotated.s, s: -rotated.q };
+        }
+        rotated
+    }
+
+    pub fn distance_to(&self, other: &Coordinate) -> i32 {
+        let diff = self.clone() - other.clone();
+        (diff.q.abs() + diff.r.abs() + diff.s.abs()) / 2
+    }
+}
+
+impl Add for Coordinate {
+    type Output = Coordinate;
+
+    fn add(self, other: Coordinate) -> Coordinate {
+        Coordinate { q: self.q + other.q, r: self.r + other.r, s: self.s + other.s }
+    }
+}
+
+impl Sub for Coordinate {
+    type Output = Coordinate;
+
+    fn sub(self, other: Coordinate) -> Coordinate {
+        Coordinate { q: self.q - other.q, r: self.r - other.r, s: self.s - other.s }
+    }
+}
+
+impl Mul<i32> for Coordinate {
+    type Output = Coordinate;
+
+    fn mul(self, factor: i32) -> Coordinate {
+        Coordinate { q: self.q * factor, r: self.r * factor, s: self.s * factor }
+    }
+}
+
+impl CubeDirection {
+    /// The six directions of the hex grid in clockwise order, starting with `Right`.
+    pub const HEX: [CubeDirection; 6] = [
+        CubeDirection::Right,
+        CubeDirection::DownRight,
+        CubeDirection::DownLeft,
+        CubeDirection::Left,
+        CubeDirection::UpLeft,
+        CubeDirection::UpRight,
+    ];
+
+    /// Stable index of the direction: its position in [`CubeDirection::HEX`], then 6 for `Up` and 7 for `Down`.
+    pub fn index(&self) -> u8 {
+        match self {
+            CubeDirection::Right => 0,
+            CubeDirection::DownRight => 1,
+            CubeDirection::DownLeft => 2,
+            CubeDirection::Left => 3,
+            CubeDirection::UpLeft => 4,
+            CubeDirection::UpRight => 5,
+            CubeDirection::Up => 6,
+            CubeDirection::Down => 7,
+        }
+    }
+
+    /// Inverse of [`CubeDirection::index`]. Only the lowest three bits are used.
+    pub fn from_index(index: u8) -> CubeDirection {
+        match index & 0b111 {
+            0 => CubeDirection::Right,
+            1 => CubeDirection::DownRight,
+            2 => CubeDirection::DownLeft,
+            3 => CubeDirection::Left,
+            4 => CubeDirection::UpLeft,
+            5 => CubeDirection::UpRight,
+            6 => CubeDirection::Up,
+            _ => CubeDirection::Down,
+        }
+    }
+
+    /// Number of clockwise turns from `Right` to this direction.
+    /// `Up` and `Down` are not hex directions and have none.
+    pub fn turns(&self) -> Option<i32> {
+        let index = self.index();
+        (index < 6).then_some(index as i32)
+    }
+
+    pub fn vector(&self) -> Option<Coordinate> {
+        self.turns().map(|turns| Coordinate::new(1, 0).rotated_by(turns))
+    }
+}
+
+impl Team {
+    pub fn opponent(&self) -> Team {
+        match self {
+            Team::One => Team::Two,
+            Team::Two => Team::One,
+        }
+    }
+}
+
+impl Segment {
+    /// Converts a position inside the segment's field arrays into a board coordinate.
+    pub fn coordinate_of(&self, column: usize, row: usize) -> Coordinate {
+        let r = row as i32 - 2;
+        let local = Coordinate::new(column as i32 - 1 - r.max(0), r);
+        local.rotated_by(self.direction.turns().unwrap_or(0)) + self.center.clone()
+    }
+
+    /// Returns the `(column, row)` index of a board coordinate inside this segment, if it belongs to it.
+    pub fn index_of(&self, coordinate: &Coordinate) -> Option<(usize, usize)> {
+        let local = (coordinate.clone() - self.center.clone()).rotated_by(-self.direction.turns().unwrap_or(0));
+        let column = local.q.max(-local.s) + 1;
+        let row = local.r + 2;
+        let in_bounds = (0..SEGMENT_COLUMNS as i32).contains(&column) && (0..SEGMENT_ROWS as i32).contains(&row);
+        in_bounds.then_some((column as usize, row as usize))
+    }
+
+    pub fn field_at(&self, coordinate: &Coordinate) -> Option<&Field> {
+        let (column, row) = self.index_of(coordinate)?;
+        self.field_array.get(column)?.fields.get(row)
+    }
+
+    /// All fields of the segment together with their board coordinates.
+    pub fn fields(&self) -> impl Iterator<Item = (Coordinate, &Field)> + '_ {
+        self.field_array.iter().enumerate().flat_map(move |(column, array)| {
+            array.fields.iter().enumerate().map(move |(row, field)| (self.coordinate_of(column, row), field))
+        })
+    }
+}
+
+impl Board {
+    pub fn segment_index(&self, coordinate: &Coordinate) -> Option<usize> {
+        self.segment.iter().position(|segment| segment.field_at(coordinate).is_some())
+    }
+
+    pub fn field_at(&self, coordinate: &Coordinate) -> Option<&Field> {
+        self.segment.iter().find_map(|segment| segment.field_at(coordinate))
+    }
+
+    /// All revealed fields of the board together with their coordinates.
+    pub fn fields(&self) -> impl Iterator<Item = (Coordinate, &Field)> + '_ {
+        self.segment.iter().flat_map(|segment| segment.fields())
+    }
+
+    /// Whether the field has current. The current runs through the center of each segment
+    /// and bends towards the following segment.
+    pub fn has_current(&self, coordinate: &Coordinate) -> bool {
+        let Some(index) = self.segment_index(coordinate) else {
+            return false;
+        };
+        let segment = &self.segment[index];
+        let next_direction = self.segment.get(index + 1).map_or(&self.next_direction, |next| &next.direction);
+        let (Some(direction), Some(next)) = (segment.direction.vector(), next_direction.vector()) else {
+            return false;
+        };
+        let center = segment.center.clone();
+        [
+            center.clone() - direction,
+            center.clone(),
+            center.clone() + next.clone(),
+            center + next * 2,
+        ]
+        .contains(coordinate)
+    }
+}
@@ -0,0 +1,80 @@
+#[cfg(test)]
+mod tests {
+    use crate::api::protocol::{Board, Coordinate, CubeDirection, Field, FieldArray, Segment, Team};
+
+    fn segment(direction: CubeDirection, center: Coordinate) -> Segment {
+        Segment {
+            direction,
+            center,
+            field_array: (0..4).map(|_| FieldArray { fields: vec![Field::Water; 5] }).collect(),
+        }
+    }
+
+    #[test]
+    fn test_rotated_by() {
+        let right = Coordinate::new(1, 0);
+        assert_eq!(right.rotated_by(1), Coordinate { q: 0, r: 1, s: -1 });
+        assert_eq!(right.rotated_by(-1), Coordinate { q: 1, r: -1, s: 0 });
+        assert_eq!(right.rotated_by(6), right);
+        assert_eq!(CubeDirection::DownLeft.vector(), Some(Coordinate { q: -1, r: 1, s: 0 }));
+        assert_eq!(CubeDirection::Up.vector(), None);
+    }
+
+    #[test]
+    fn test_segment_coordinates() {
+        let segment = segment(CubeDirection::Right, Coordinate::new(0, 0));
+        assert_eq!(segment.coordinate_of(1, 2), Coordinate::new(0, 0));
+        assert_eq!(segment.coordinate_of(0, 0), Coordinate::new(-1, -2));
+        assert_eq!(segment.coordinate_of(0, 4), Coordinate::new(-3, 2));
+        assert_eq!(segment.coordinate_of(3, 2), Coordinate::new(2, 0));
+
+        let rotated = self::segment(CubeDirection::DownRight, Coordinate::new(4, 0));
+        for column in 0..4 {
+            for row in 0..5 {
+                assert_eq!(segment.index_of(&segment.coordinate_of(column, row)), Some((column, row)));
+                assert_eq!(rotated.index_of(&rotated.coordinate_of(column, row)), Some((column, row)));
+            }
+        }
+        assert_eq!(segment.index_of(&Coordinate::new(3, 0)), None);
+        assert_eq!(segment.fields().count(), 20);
+    }
+
+    #[test]
+    fn test_field_at() {
+        let mut first = segment(CubeDirection::Right, Coordinate::new(0, 0));
+        first.field_array[2].fields[1] = Field::Island;
+        let board = Board {
+            next_direction: CubeDirection::Right,
+            segment: vec![first, segment(CubeDirection::Right, Coordinate::new(4, 0))],
+        };
+
+        assert_eq!(board.field_at(&Coordinate::new(1, -1)), Some(&Field::Island));
+        assert_eq!(board.field_at(&Coordinate::new(5, 0)), Some(&Field::Water));
+        assert_eq!(board.field_at(&Coordinate::new(9, 0)), None);
+        assert_eq!(board.segment_index(&Coordinate::new(4, 1)), Some(1));
+    }
+
+    #[test]
+    fn test_has_current() {
+        let board = Board {
+            next_direction: CubeDirection::DownRight,
+            segment: vec![
+                segment(CubeDirection::Right, Coordinate::new(0, 0)),
+                segment(CubeDirection::Right, Coordinate::new(4, 0)),
+            ],
+        };
+
+        assert!(board.has_current(&Coordinate::new(-1, 0)));
+        assert!(board.has_current(&Coordinate::new(2, 0)));
+        assert!(!board.has_current(&Coordinate::new(0, 1)));
+        assert!(board.has_current(&Coordinate::new(4, 1)));
+        assert!(board.has_current(&Coordinate::new(4, 2)));
+        assert!(!board.has_current(&Coordinate::new(6, 0)));
+    }
+
+    #[test]
+    fn test_team() {
+        assert_eq!(Team::One.opponent(), Team::Two);
+        assert_eq!(Team::Two.opponent(), Team::One);
+    }
+}
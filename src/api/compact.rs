@@ -0,0 +1,319 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::api::board::{SEGMENT_COLUMNS, SEGMENT_ROWS};
+use crate::api::protocol::{Board, Coordinate, CubeDirection, Field, FieldArray, Passenger, Segment, Ship, State, Team};
+
+pub const MAX_SEGMENTS: usize = 8;
+pub const FIELDS_PER_SEGMENT: usize = SEGMENT_COLUMNS * SEGMENT_ROWS;
+pub const MAX_FIELDS: usize = MAX_SEGMENTS * FIELDS_PER_SEGMENT;
+pub const MAX_SHIPS: usize = 2;
+
+const EMPTY: u8 = 0;
+const WATER: u8 = 1;
+const ISLAND: u8 = 2;
+const SANDBANK: u8 = 3;
+const GOAL: u8 = 4;
+const PASSENGER: u8 = 5;
+
+/// Search-oriented copy of a [`State`].
+///
+/// Fields live in one fixed-size array indexed by a dense field id
+/// (`segment * 20 + column * 5 + row`), each encoded in a byte: bits 0-2 hold the field type,
+/// bits 3-5 the direction of a passenger dock and bits 6-7 its passengers. Ships and segments
+/// are packed into integers. Converting from and back to a `State` is lossless; states that
+/// do not fit the limits (at most 8 segments of 4x5 fields, 2 ships, values in the packed
+/// ranges) are rejected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct CompactState {
+    pub fields: [u8; MAX_FIELDS],
+    pub segments: [PackedSegment; MAX_SEGMENTS],
+    pub segment_count: u8,
+    pub ships: [PackedShip; MAX_SHIPS],
+    pub ship_count: u8,
+    pub turn: u16,
+    pub start_team: u8,
+    pub current_team: u8,
+    pub next_direction: u8,
+}
+
+/// Direction in bits 0-2, center `q` in bits 8-15 and `r` in bits 16-23.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct PackedSegment(pub u32);
+
+/// Ship packed into a `u64`:
+///
+/// | bits  | 0-7 | 8-15 | 16-18     | 19-22 | 23-27 | 28-31      | 32-34      | 35   | 48-63  |
+/// |-------|-----|------|-----------|-------|-------|------------|------------|------|--------|
+/// | value | q   | r    | direction | speed | coal  | passengers | free turns | team | points |
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct PackedShip(pub u64);
+
+#[derive(Debug, PartialEq)]
+pub enum CompactError {
+    TooManySegments(usize),
+    TooManyShips(usize),
+    SegmentShape(usize),
+    OutOfRange(&'static str, i32),
+    InvalidCoordinate(Coordinate),
+    UnknownClass(String),
+}
+
+impl Error for CompactError {}
+
+impl fmt::Display for CompactError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CompactError::TooManySegments(count) => write!(f, "Too many segments: {}", count),
+            CompactError::TooManyShips(count) => write!(f, "Too many ships: {}", count),
+            CompactError::SegmentShape(index) => write!(f, "Segment {} is not 4x5 fields", index),
+            CompactError::OutOfRange(name, value) => write!(f, "Value of {} out of range: {}", name, value),
+            CompactError::InvalidCoordinate(coordinate) => write!(f, "Invalid cube coordinate: {}", coordinate),
+            CompactError::UnknownClass(class) => write!(f, "Unknown state class: {}", class),
+        }
+    }
+}
+
+impl PackedSegment {
+    pub fn direction(&self) -> CubeDirection {
+        CubeDirection::from_index(self.0 as u8 & 0b111)
+    }
+
+    pub fn center(&self) -> Coordinate {
+        Coordinate::new((self.0 >> 8) as u8 as i8 as i32, (self.0 >> 16) as u8 as i8 as i32)
+    }
+}
+
+impl PackedShip {
+    pub fn position(&self) -> Coordinate {
+        Coordinate::new(self.0 as u8 as i8 as i32, (self.0 >> 8) as u8 as i8 as i32)
+    }
+
+    pub fn direction(&self) -> CubeDirection {
+        CubeDirection::from_index((self.0 >> 16) as u8 & 0b111)
+    }
+
+    pub fn speed(&self) -> i32 {
+        ((self.0 >> 19) & 0b1111) as i32
+    }
+
+    pub fn coal(&self) -> i32 {
+        ((self.0 >> 23) & 0b1_1111) as i32
+    }
+
+    pub fn passengers(&self) -> i32 {
+        ((self.0 >> 28) & 0b1111) as i32
+    }
+
+    pub fn free_turns(&self) -> i32 {
+        ((self.0 >> 32) & 0b111) as i32
+    }
+
+    pub fn team(&self) -> Team {
+        if (self.0 >> 35) & 1 == 0 {
+            Team::One
+        } else {
+            Team::Two
+        }
+    }
+
+    pub fn points(&self) -> i32 {
+        (self.0 >> 48) as u16 as i16 as i32
+    }
+}
+
+impl TryFrom<&Ship> for PackedShip {
+    type Error = CompactError;
+
+    fn try_from(ship: &Ship) -> Result<Self, Self::Error> {
+        let (q, r) = pack_coordinate(&ship.position)?;
+        let value = q as u64
+            | (r as u64) << 8
+            | (ship.direction.index() as u64) << 16
+            | (bounded("speed", ship.speed, 15)? as u64) << 19
+            | (bounded("coal", ship.coal, 31)? as u64) << 23
+            | (bounded("passengers", ship.passengers, 15)? as u64) << 28
+            | (bounded("free turns", ship.free_turns, 7)? as u64) << 32
+            | (team_value(&ship.team) as u64) << 35
+            | (i16::try_from(ship.points).map_err(|_| CompactError::OutOfRange("points", ship.points))? as u16 as u64) << 48;
+        Ok(PackedShip(value))
+    }
+}
+
+impl From<PackedShip> for Ship {
+    fn from(packed: PackedShip) -> Self {
+        Ship {
+            team: packed.team(),
+            direction: packed.direction(),
+            speed: packed.speed(),
+            coal: packed.coal(),
+            passengers: packed.passengers(),
+            free_turns: packed.free_turns(),
+            points: packed.points(),
+            position: packed.position(),
+        }
+    }
+}
+
+impl CompactState {
+    /// Dense id of the field at `coordinate`, if it lies on a revealed segment.
+    pub fn field_id(&self, coordinate: &Coordinate) -> Option<usize> {
+        (0..self.segment_count as usize).find_map(|index| {
+            let (column, row) = self.segment(index).index_of(coordinate)?;
+            Some(index * FIELDS_PER_SEGMENT + column * SEGMENT_ROWS + row)
+        })
+    }
+
+    /// Board coordinate of a dense field id.
+    pub fn coordinate(&self, id: usize) -> Coordinate {
+        let segment = self.segment(id / FIELDS_PER_SEGMENT);
+        let offset = id % FIELDS_PER_SEGMENT;
+        segment.coordinate_of(offset / SEGMENT_ROWS, offset % SEGMENT_ROWS)
+    }
+
+    pub fn field(&self, id: usize) -> Option<Field> {
+        field_from(self.fields[id])
+    }
+
+    /// Segment geometry without fields, for coordinate calculations.
+    fn segment(&self, index: usize) -> Segment {
+        let packed = self.segments[index];
+        Segment { direction: packed.direction(), center: packed.center(), field_array: Vec::new() }
+    }
+}
+
+impl TryFrom<&State> for CompactState {
+    type Error = CompactError;
+
+    fn try_from(state: &State) -> Result<Self, Self::Error> {
+        if state.class != "state" {
+            return Err(CompactError::UnknownClass(state.class.clone()));
+        }
+        if state.board.segment.len() > MAX_SEGMENTS {
+            return Err(CompactError::TooManySegments(state.board.segment.len()));
+        }
+        if state.ship.len() > MAX_SHIPS {
+            return Err(CompactError::TooManyShips(state.ship.len()));
+        }
+
+        let mut compact = CompactState {
+            fields: [EMPTY; MAX_FIELDS],
+            segments: [PackedSegment::default(); MAX_SEGMENTS],
+            segment_count: state.board.segment.len() as u8,
+            ships: [PackedShip::default(); MAX_SHIPS],
+            ship_count: state.ship.len() as u8,
+            turn: u16::try_from(state.turn).map_err(|_| CompactError::OutOfRange("turn", state.turn))?,
+            start_team: team_value(&state.start_team),
+            current_team: team_value(&state.current_team),
+            next_direction: state.board.next_direction.index(),
+        };
+
+        for (index, segment) in state.board.segment.iter().enumerate() {
+            let well_formed = segment.field_array.len() == SEGMENT_COLUMNS
+                && segment.field_array.iter().all(|column| column.fields.len() == SEGMENT_ROWS);
+            if !well_formed {
+                return Err(CompactError::SegmentShape(index));
+            }
+            let (q, r) = pack_coordinate(&segment.center)?;
+            compact.segments[index] = PackedSegment(segment.direction.index() as u32 | (q as u32) << 8 | (r as u32) << 16);
+
+            for (column, array) in segment.field_array.iter().enumerate() {
+                for (row, field) in array.fields.iter().enumerate() {
+                    compact.fields[index * FIELDS_PER_SEGMENT + column * SEGMENT_ROWS + row] = field_value(field)?;
+                }
+            }
+        }
+
+        for (index, ship) in state.ship.iter().enumerate() {
+            compact.ships[index] = PackedShip::try_from(ship)?;
+        }
+
+        Ok(compact)
+    }
+}
+
+impl From<&CompactState> for State {
+    fn from(compact: &CompactState) -> Self {
+        let segment = (0..compact.segment_count as usize)
+            .map(|index| {
+                let packed = compact.segments[index];
+                let field_array = (0..SEGMENT_COLUMNS)
+                    .map(|column| {
+                        let start = index * FIELDS_PER_SEGMENT + column * SEGMENT_ROWS;
+                        let fields = compact.fields[start..start + SEGMENT_ROWS].iter().filter_map(|&value| field_from(value)).collect();
+                        FieldArray { fields }
+                    })
+                    .collect();
+                Segment { direction: packed.direction(), center: packed.center(), field_array }
+            })
+            .collect();
+
+        State {
+            class: "state".to_string(),
+            start_team: team_from(compact.start_team),
+            turn: compact.turn as i32,
+            current_team: team_from(compact.current_team),
+            board: Board { next_direction: CubeDirection::from_index(compact.next_direction), segment },
+            ship: compact.ships[..compact.ship_count as usize].iter().map(|&ship| Ship::from(ship)).collect(),
+        }
+    }
+}
+
+fn bounded(name: &'static str, value: i32, max: i32) -> Result<i32, CompactError> {
+    if (0..=max).contains(&value) {
+        Ok(value)
+    } else {
+        Err(CompactError::OutOfRange(name, value))
+    }
+}
+
+fn pack_coordinate(coordinate: &Coordinate) -> Result<(u8, u8), CompactError> {
+    let invalid = || CompactError::InvalidCoordinate(coordinate.clone());
+    if coordinate.q + coordinate.r + coordinate.s != 0 {
+        return Err(invalid());
+    }
+    let q = i8::try_from(coordinate.q).map_err(|_| invalid())?;
+    let r = i8::try_from(coordinate.r).map_err(|_| invalid())?;
+    Ok((q as u8, r as u8))
+}
+
+fn field_value(field: &Field) -> Result<u8, CompactError> {
+    Ok(match field {
+        Field::Water => WATER,
+        Field::Island => ISLAND,
+        Field::Sandbank => SANDBANK,
+        Field::Goal => GOAL,
+        Field::Passenger(passenger) => {
+            PASSENGER | passenger.direction.index() << 3 | (bounded("dock passengers", passenger.passenger, 3)? as u8) << 6
+        }
+    })
+}
+
+fn field_from(value: u8) -> Option<Field> {
+    match value & 0b111 {
+        WATER => Some(Field::Water),
+        ISLAND => Some(Field::Island),
+        SANDBANK => Some(Field::Sandbank),
+        GOAL => Some(Field::Goal),
+        PASSENGER => Some(Field::Passenger(Passenger {
+            direction: CubeDirection::from_index(value >> 3 & 0b111),
+            passenger: (value >> 6) as i32,
+        })),
+        _ => None,
+    }
+}
+
+fn team_value(team: &Team) -> u8 {
+    match team {
+        Team::One => 0,
+        Team::Two => 1,
+    }
+}
+
+fn team_from(value: u8) -> Team {
+    if value == 0 {
+        Team::One
+    } else {
+        Team::Two
+    }
+}
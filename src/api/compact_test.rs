@@ -0,0 +1,58 @@
+#[cfg(test)]
+mod tests {
+    use crate::api::compact::{CompactError, CompactState, PackedShip};
+    use crate::api::protocol::{Coordinate, CubeDirection, Ship, State, Team};
+
+    const NOTATION: &str = "1 2 17 UR R@0,0:wwwww|wiwww|pUL1swwg|wwwww/DR@2,4:wwwww|pR0wwww|wwwww|ggggg 1R@-1,-1:1,6,0,1,0 2DL@3,4:4,0,2,3,-12";
+
+    #[test]
+    fn test_state_round_trip() {
+        let state = State::from_notation(NOTATION).unwrap();
+        let compact = CompactState::try_from(&state).unwrap();
+        assert_eq!(State::from(&compact), state);
+    }
+
+    #[test]
+    fn test_field_ids() {
+        let state = State::from_notation(NOTATION).unwrap();
+        let compact = CompactState::try_from(&state).unwrap();
+        for (coordinate, field) in state.board.fields() {
+            let id = compact.field_id(&coordinate).unwrap();
+            assert_eq!(compact.coordinate(id), coordinate);
+            assert_eq!(compact.field(id).as_ref(), Some(field));
+        }
+        assert_eq!(compact.field_id(&Coordinate::new(20, 0)), None);
+    }
+
+    #[test]
+    fn test_packed_ship() {
+        let ship = Ship {
+            team: Team::Two,
+            direction: CubeDirection::UpLeft,
+            speed: 6,
+            coal: 0,
+            passengers: 2,
+            free_turns: 1,
+            points: 123,
+            position: Coordinate::new(-17, 9),
+        };
+        let packed = PackedShip::try_from(&ship).unwrap();
+        assert_eq!(packed.points(), 123);
+        assert_eq!(Ship::from(packed), ship);
+    }
+
+    #[test]
+    fn test_rejects_unrepresentable_states() {
+        let mut state = State::from_notation(NOTATION).unwrap();
+        state.ship[0].speed = 16;
+        assert_eq!(CompactState::try_from(&state), Err(CompactError::OutOfRange("speed", 16)));
+
+        let mut state = State::from_notation(NOTATION).unwrap();
+        state.board.segment[1].field_array.pop();
+        assert_eq!(CompactState::try_from(&state), Err(CompactError::SegmentShape(1)));
+
+        let mut state = State::from_notation(NOTATION).unwrap();
+        state.ship[1].position.s = 0;
+        assert!(matches!(CompactState::try_from(&state), Err(CompactError::InvalidCoordinate(_))));
+    }
+}
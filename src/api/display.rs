@@ -0,0 +1,183 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::api::protocol::{Board, Coordinate, CubeDirection, Field, Ship, State, Team};
+
+impl fmt::Display for Team {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Team::One => write!(f, "ONE"),
+            Team::Two => write!(f, "TWO"),
+        }
+    }
+}
+
+impl FromStr for Team {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ONE" => Ok(Team::One),
+            "TWO" => Ok(Team::Two),
+            _ => Err(format!("Unknown team: {}", s)),
+        }
+    }
+}
+
+impl fmt::Display for CubeDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            CubeDirection::Right => "RIGHT",
+            CubeDirection::UpRight => "UP_RIGHT",
+            CubeDirection::Up => "UP",
+            CubeDirection::UpLeft => "UP_LEFT",
+            CubeDirection::Left => "LEFT",
+            CubeDirection::DownLeft => "DOWN_LEFT",
+            CubeDirection::Down => "DOWN",
+            CubeDirection::DownRight => "DOWN_RIGHT",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl FromStr for CubeDirection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "RIGHT" => Ok(CubeDirection::Right),
+            "UP_RIGHT" => Ok(CubeDirection::UpRight),
+            "UP" => Ok(CubeDirection::Up),
+            "UP_LEFT" => Ok(CubeDirection::UpLeft),
+            "LEFT" => Ok(CubeDirection::Left),
+            "DOWN_LEFT" => Ok(CubeDirection::DownLeft),
+            "DOWN" => Ok(CubeDirection::Down),
+            "DOWN_RIGHT" => Ok(CubeDirection::DownRight),
+            _ => Err(format!("Unknown direction: {}", s)),
+        }
+    }
+}
+
+impl CubeDirection {
+    pub fn arrow(&self) -> char {
+        match self {
+            CubeDirection::Right => '→',
+            CubeDirection::UpRight => '↗',
+            CubeDirection::Up => '↑',
+            CubeDirection::UpLeft => '↖',
+            CubeDirection::Left => '←',
+            CubeDirection::DownLeft => '↙',
+            CubeDirection::Down => '↓',
+            CubeDirection::DownRight => '↘',
+        }
+    }
+}
+
+impl fmt::Display for Coordinate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {}, {})", self.q, self.r, self.s)
+    }
+}
+
+impl fmt::Display for Ship {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} at {} {} {}: speed {}, coal {}, passengers {}, free turns {}, points {}",
+            self.team,
+            self.position,
+            self.direction.arrow(),
+            self.direction,
+            self.speed,
+            self.coal,
+            self.passengers,
+            self.free_turns,
+            self.points
+        )
+    }
+}
+
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", render(self, &[]))
+    }
+}
+
+impl fmt::Display for State {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Turn {}, {} to move", self.turn, self.current_team)?;
+        write!(f, "{}", render(&self.board, &self.ship))?;
+        for ship in &self.ship {
+            writeln!(f, "{}", ship)?;
+        }
+        Ok(())
+    }
+}
+
+/// Draws the board as rows of hex cells, three characters per cell:
+///
+/// ` . ` water, ` ~ ` current, ` # ` island, ` = ` sandbank, ` G ` goal,
+/// `P↗2` passenger dock facing up right with two passengers, `1→~` ship of team ONE heading right on current.
+fn render(board: &Board, ships: &[Ship]) -> String {
+    let mut cells: HashMap<(i32, i32), String> = HashMap::new();
+    for (coordinate, field) in board.fields() {
+        let symbol = field_symbol(board, &coordinate, field);
+        let cell = match field {
+            Field::Passenger(passenger) => {
+                format!("P{}{}", passenger.direction.arrow(), passenger.passenger.clamp(0, 9))
+            }
+            _ => format!(" {} ", symbol),
+        };
+        cells.insert((coordinate.q, coordinate.r), cell);
+    }
+    for ship in ships {
+        let position = &ship.position;
+        let symbol = board.field_at(position).map_or(' ', |field| field_symbol(board, position, field));
+        let team = match ship.team {
+            Team::One => '1',
+            Team::Two => '2',
+        };
+        cells.insert((position.q, position.r), format!("{}{}{}", team, ship.direction.arrow(), symbol));
+    }
+
+    if cells.is_empty() {
+        return String::new();
+    }
+    let min_r = cells.keys().map(|&(_, r)| r).min().unwrap_or(0);
+    let max_r = cells.keys().map(|&(_, r)| r).max().unwrap_or(0);
+    let min_x = cells.keys().map(|&(q, r)| 2 * q + r).min().unwrap_or(0);
+
+    let mut output = String::new();
+    for r in min_r..=max_r {
+        let mut row: Vec<(i32, &String)> = cells
+            .iter()
+            .filter(|((_, cell_r), _)| *cell_r == r)
+            .map(|(&(q, _), cell)| (2 * q + r - min_x, cell))
+            .collect();
+        row.sort_by_key(|&(x, _)| x);
+
+        let mut line = String::new();
+        let mut width = 0;
+        for (x, cell) in row {
+            let column = 2 * x as usize;
+            line.push_str(&" ".repeat(column.saturating_sub(width)));
+            line.push_str(cell);
+            width = column + cell.chars().count();
+        }
+        output.push_str(line.trim_end());
+        output.push('\n');
+    }
+    output
+}
+
+fn field_symbol(board: &Board, coordinate: &Coordinate, field: &Field) -> char {
+    match field {
+        Field::Water if board.has_current(coordinate) => '~',
+        Field::Water => '.',
+        Field::Island => '#',
+        Field::Sandbank => '=',
+        Field::Passenger(_) => 'P',
+        Field::Goal => 'G',
+    }
+}
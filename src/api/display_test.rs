@@ -0,0 +1,66 @@
+#[cfg(test)]
+mod tests {
+    use crate::api::protocol::{Board, Coordinate, CubeDirection, Field, FieldArray, Passenger, Segment, Ship, State, Team};
+
+    fn state() -> State {
+        let mut columns: Vec<FieldArray> = (0..4).map(|_| FieldArray { fields: vec![Field::Water; 5] }).collect();
+        columns[2].fields[0] = Field::Island;
+        columns[3].fields[4] = Field::Sandbank;
+        columns[1].fields[0] = Field::Passenger(Passenger { direction: CubeDirection::DownRight, passenger: 1 });
+        State {
+            class: "state".to_string(),
+            start_team: Team::One,
+            turn: 3,
+            current_team: Team::Two,
+            board: Board {
+                next_direction: CubeDirection::Right,
+                segment: vec![Segment { direction: CubeDirection::Right, center: Coordinate::new(0, 0), field_array: columns }],
+            },
+            ship: vec![
+                Ship { team: Team::One, direction: CubeDirection::Right, speed: 2, coal: 5, passengers: 1, free_turns: 1, points: 7, position: Coordinate::new(0, 0) },
+                Ship { team: Team::Two, direction: CubeDirection::UpRight, speed: 1, coal: 6, passengers: 0, free_turns: 0, points: 3, position: Coordinate::new(-2, 1) },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_direction_round_trip() {
+        for direction in CubeDirection::HEX {
+            assert_eq!(direction.to_string().parse::<CubeDirection>(), Ok(direction));
+        }
+        assert_eq!("TWO".parse::<Team>(), Ok(Team::Two));
+        assert!("NORTH".parse::<CubeDirection>().is_err());
+    }
+
+    #[test]
+    fn test_ship_display() {
+        let state = state();
+        assert_eq!(
+            state.ship[0].to_string(),
+            "ONE at (0, 0, 0) → RIGHT: speed 2, coal 5, passengers 1, free turns 1, points 7"
+        );
+    }
+
+    #[test]
+    fn test_board_display() {
+        let board = state().board.to_string();
+        let lines: Vec<&str> = board.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[0], " .  P↘1  #   .");
+        assert_eq!(lines[1], "   .   .   .   .");
+        assert_eq!(lines[2], "     ~   ~   ~   ~");
+        assert_eq!(lines[3], "   .   .   .   .");
+        assert_eq!(lines[4], " .   .   .   =");
+    }
+
+    #[test]
+    fn test_state_display() {
+        let output = state().to_string();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines[0], "Turn 3, TWO to move");
+        assert_eq!(lines[3], "     ~  1→~  ~   ~");
+        assert_eq!(lines[4], "  2↗.  .   .   .");
+        assert!(lines[6].starts_with("ONE at (0, 0, 0)"));
+        assert!(lines[7].starts_with("TWO at (-2, 1, 1)"));
+    }
+}
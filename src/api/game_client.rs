@@ -2,22 +2,61 @@ use std::error::Error;
 use std::io::{Read, Write};
 use std::net::TcpStream;
 use std::str;
+use std::time::Instant;
 
 use yaserde::de::from_str;
-use yaserde::ser::to_string;
 
 use crate::api::protocol::*;
+use crate::api::recorder::Recorder;
 use crate::api::xml_buffer::XmlBuffer;
+use crate::search::time_manager::{TimeConfig, TimeManager};
 
+/// Callbacks of a bot. Every packet of a room comes with the [`GameContext`], so the bot can
+/// tell its own ship by [`GameContext::my_team`] without storing the team itself.
 pub trait ClientInterface {
-    fn on_state(&mut self, state: Room);
-    fn on_move_request(&mut self) -> Move;
+    fn on_state(&mut self, state: Room, context: &GameContext);
+    fn on_move_request(&mut self, context: &GameContext) -> Move;
     fn on_error_packet(&mut self, error_packet: ErrorPacket);
-    fn on_erroneous_move(&mut self, error: Room);
+    fn on_erroneous_move(&mut self, error: Room, context: &GameContext);
     fn on_joined(&mut self, joined: Joined);
-    fn on_welcome(&mut self, welcome_message: Room);
+    /// The context already holds the team of the welcome message.
+    fn on_welcome(&mut self, welcome_message: Room, context: &GameContext);
     fn on_left(&mut self, left: Left);
-    fn on_result(&mut self, result: Room);
+    fn on_result(&mut self, result: Room, context: &GameContext);
+
+    /// Called after a move was sent if pondering is enabled. The bot may start a background
+    /// search, but must return immediately.
+    fn on_ponder_start(&mut self) {}
+
+    /// Called after `on_ponder_start` once the opponent has moved, i.e. on a move request or a
+    /// memento with the own team to move, and when the game or the connection ends. It comes
+    /// before that packet is handled. The background search must be stopped before this returns.
+    fn on_ponder_stop(&mut self) {}
+}
+
+/// What the [`GameClient`] knows about the game besides the packets themselves.
+#[derive(Clone, Debug, Default)]
+pub struct GameContext {
+    /// Fed with the measured time between sending a move and receiving the next packet, so
+    /// [`TimeManager::budget`] answers how long the bot may think about this move.
+    pub time_manager: TimeManager,
+    /// The own team, known once the welcome message was received.
+    pub team: Option<Team>,
+}
+
+impl GameContext {
+    pub fn my_team(&self) -> Option<Team> {
+        self.team.clone()
+    }
+
+    pub fn opponent_team(&self) -> Option<Team> {
+        self.team.as_ref().map(Team::opponent)
+    }
+
+    /// Whether the state has the own team to move.
+    pub fn is_my_turn(&self, state: &State) -> bool {
+        self.team.as_ref() == Some(&state.current_team)
+    }
 }
 
 pub struct GameClient {
@@ -26,8 +65,13 @@ pub struct GameClient {
     reservation_code: Option<String>,
     room_id: Option<String>,
     client_interface: Box<dyn ClientInterface>,
-    stream: TcpStream,
+    stream: Option<TcpStream>,
     connected: bool,
+    ponder: bool,
+    pondering: bool,
+    recorder: Option<Recorder>,
+    context: GameContext,
+    move_sent: Option<Instant>,
 }
 
 impl GameClient {
@@ -38,69 +82,152 @@ impl GameClient {
         room_id: Option<String>,
         client_interface: Box<dyn ClientInterface>,
     ) -> Result<Self, std::io::Error> {
-        let stream = TcpStream::connect((host.as_str(), port))?;
-        let connected = false;
         Ok(GameClient {
             host,
             port,
             reservation_code,
             room_id,
             client_interface,
-            stream,
-            connected,
+            stream: None,
+            connected: false,
+            ponder: false,
+            pondering: false,
+            recorder: None,
+            context: GameContext::default(),
+            move_sent: None,
         })
     }
 
+    /// Records all packets of the game, see [`Recorder`].
+    pub fn set_recorder(&mut self, recorder: Recorder) {
+        self.recorder = Some(recorder);
+    }
+
+    /// Lets the client interface search on the opponent's turn, see
+    /// [`ClientInterface::on_ponder_start`].
+    pub fn set_ponder(&mut self, ponder: bool) {
+        self.ponder = ponder;
+    }
+
+    /// Sets the time limits the [`TimeManager`] of the context plans with.
+    pub fn set_time_config(&mut self, config: TimeConfig) {
+        self.context.time_manager.config = config;
+    }
+
+    pub fn context(&self) -> &GameContext {
+        &self.context
+    }
+
+    /// The own team, known once the welcome message was received.
+    pub fn my_team(&self) -> Option<Team> {
+        self.context.my_team()
+    }
+
+    pub fn opponent_team(&self) -> Option<Team> {
+        self.context.opponent_team()
+    }
+
+    /// Connects to the server, joins and handles packets until the client left the game or the
+    /// server closed the connection.
     pub fn run(&mut self) -> Result<(), std::io::Error> {
-        self.stream = TcpStream::connect((self.host.as_str(), self.port))?;
+        let mut stream = TcpStream::connect((self.host.as_str(), self.port))?;
         let mut buffer = XmlBuffer::new();
+        self.stream = Some(stream.try_clone()?);
 
-        self.handle_join();
+        self.handle_join()?;
         self.connected = true;
+        let result = self.read_packets(&mut stream, &mut buffer);
+        self.stop_pondering();
+
+        if let Some(recorder) = self.recorder.as_mut() {
+            recorder.finish()?;
+        }
+        result
+    }
+
+    fn read_packets(&mut self, stream: &mut TcpStream, buffer: &mut XmlBuffer) -> Result<(), std::io::Error> {
         loop {
-            if self.connected == false {
+            if !self.connected {
                 break;
             }
             let mut buf = [0; 16129];
-            let bytes_read = self.stream.read(&mut buf)?;
+            let bytes_read = stream.read(&mut buf)?;
+            if bytes_read == 0 {
+                self.connected = false;
+                break;
+            }
             buffer.push(&buf[0..bytes_read]);
 
             while let Some(xml_bytes) = buffer.pop_xml() {
-                let xml_str = str::from_utf8(&xml_bytes).unwrap().to_string();
-                self.handle_protocol(xml_str).expect("Exception: Could not handle protocol");
+                let xml_str = String::from_utf8_lossy(&xml_bytes).into_owned();
+                self.handle_protocol(xml_str)?;
             }
         }
-
         Ok(())
     }
 
+    fn stop_pondering(&mut self) {
+        if self.pondering {
+            self.client_interface.on_ponder_stop();
+            self.pondering = false;
+        }
+    }
+
+    /// Whether the packet shows that the opponent's turn is over. The memento right after the
+    /// own move still has the opponent to move.
+    fn opponent_moved(&self, room: &Room) -> bool {
+        match room.data.class {
+            DataClass::MoveRequest | DataClass::Result => true,
+            DataClass::Memento => room.data.state.as_ref().is_some_and(|state| self.context.is_my_turn(state)),
+            _ => false,
+        }
+    }
+
     fn handle_protocol(&mut self, xml_str: String) -> Result<(), std::io::Error> {
+        if let Some(sent) = self.move_sent.take() {
+            self.context.time_manager.record_round_trip(sent.elapsed());
+        }
         println!("Received: \n{}", xml_str);
+        if let Some(recorder) = self.recorder.as_mut() {
+            recorder.received(&xml_str)?;
+        }
 
         let packet = match ProtocolPacket::parse(xml_str.as_str()) {
             Ok(packet) => packet,
-            Err(error) => return Err(std::io::Error::new(std::io::ErrorKind::Other, error.to_string())),
+            Err(error) => return Err(std::io::Error::other(error.to_string())),
         };
 
         match packet {
             ProtocolPacket::Room(room) => {
+                if self.opponent_moved(&room) {
+                    self.stop_pondering();
+                }
                 match room.data.class {
                     DataClass::MoveRequest => {
-                        let _move = self.client_interface.on_move_request();
+                        let _move = self.client_interface.on_move_request(&self.context);
                         let data = Data::new_move(_move);
                         self.send_room(room.room_id, data)?;
+                        self.move_sent = Some(Instant::now());
+                        if self.ponder {
+                            self.client_interface.on_ponder_start();
+                            self.pondering = true;
+                        }
+                    }
+                    DataClass::Memento => self.client_interface.on_state(room, &self.context),
+                    DataClass::Result => self.client_interface.on_result(room, &self.context),
+                    DataClass::WelcomeMessage => {
+                        self.context.team = room.data.team();
+                        self.client_interface.on_welcome(room, &self.context)
                     }
-                    DataClass::Memento => self.client_interface.on_state(room),
-                    DataClass::Result => self.client_interface.on_result(room),
-                    DataClass::WelcomeMessage => self.client_interface.on_welcome(room),
                     DataClass::Move => {}
-                    DataClass::Error => self.client_interface.on_erroneous_move(room)
+                    DataClass::Error => self.client_interface.on_erroneous_move(room, &self.context)
                 }
             }
             ProtocolPacket::Joined(joined) => {
                 self.client_interface.on_joined(joined);
             }
             ProtocolPacket::Left(left) => {
+                self.stop_pondering();
                 self.client_interface.on_left(left);
                 self.connected = false;
             }
@@ -115,25 +242,29 @@ impl GameClient {
 
 
     fn send(&mut self, buf: &[u8]) -> std::io::Result<()> {
-        println!("Sending: \n{}", str::from_utf8(&buf).unwrap());
-        self.stream.write_all(&buf)?;
-        Ok(())
+        println!("Sending: \n{}", str::from_utf8(buf).unwrap());
+        if let Some(recorder) = self.recorder.as_mut() {
+            recorder.sent(&String::from_utf8_lossy(buf))?;
+        }
+        match self.stream.as_mut() {
+            Some(stream) => stream.write_all(buf),
+            None => Err(std::io::Error::from(std::io::ErrorKind::NotConnected)),
+        }
     }
 
     fn send_room(&mut self, room_id: String, data: Data) -> Result<(), std::io::Error> {
         let room = Room { data, room_id };
-        let substring_to_remove = r#"<?xml version="1.0" encoding="utf-8"?>"#;
-        let serialized_item = to_string(&room).unwrap().replace(substring_to_remove, "");
+        let serialized_item = to_xml(&room).map_err(std::io::Error::other)?;
         self.send(serialized_item.as_ref())
     }
 
-    fn handle_join(&mut self) {
-        if self.reservation_code.is_some() {
-            self.join_reservation(self.reservation_code.clone().unwrap()).expect("Exception: Could not join with reservation code");
-        } else if self.room_id.is_some() {
-            self.join_room(self.room_id.clone().unwrap()).expect("Exception: Could not join with room id");
+    fn handle_join(&mut self) -> Result<(), std::io::Error> {
+        if let Some(reservation_code) = self.reservation_code.clone() {
+            self.join_reservation(reservation_code)
+        } else if let Some(room_id) = self.room_id.clone() {
+            self.join_room(room_id)
         } else {
-            self.join().expect("Exception: Could not join");
+            self.join()
         }
     }
     fn join(&mut self) -> Result<(), std::io::Error> {
@@ -143,13 +274,13 @@ impl GameClient {
 
     fn join_reservation(&mut self, reservation_code: String) -> Result<(), std::io::Error> {
         let join = JoinPrepared { reservation_code };
-        let serialized_item = to_string(&join).unwrap();
+        let serialized_item = format!("<protocol>{}", to_xml(&join).map_err(std::io::Error::other)?);
         self.send(serialized_item.as_ref())
     }
 
-    fn join_room(&mut self, _room_id: String) -> Result<(), std::io::Error> {
-        let join = Join {};
-        let serialized_item = to_string(&join).unwrap();
+    fn join_room(&mut self, room_id: String) -> Result<(), std::io::Error> {
+        let join = JoinRoom { room_id };
+        let serialized_item = format!("<protocol>{}", to_xml(&join).map_err(std::io::Error::other)?);
         self.send(serialized_item.as_ref())
     }
 }
@@ -166,43 +297,62 @@ impl Data {
             winner: None,
         }
     }
+
+    /// The team of a welcome message.
+    pub fn team(&self) -> Option<Team> {
+        self.color.as_deref().and_then(|color| color.parse().ok())
+    }
 }
 
 impl ProtocolPacket {
     pub fn parse(xml_str: &str) -> Result<ProtocolPacket, ProtocolError> {
-        match xml_str {
-            s if s.contains("joined") => {
-                let joined: Joined = from_str(&xml_str)
+        // The first packet of a connection may still carry the opening tag of the stream.
+        let xml_str = xml_str.trim_start();
+        let xml_str = xml_str.strip_prefix("<protocol>").unwrap_or(xml_str);
+        // Dispatch on the root element only; an error packet contains the name of the request.
+        let root = xml_str
+            .split('<')
+            .map(|tag| tag.split(|c: char| c.is_whitespace() || c == '/' || c == '>').next().unwrap_or(""))
+            .find(|name| !name.is_empty() && !name.starts_with('?') && !name.starts_with('!'))
+            .unwrap_or("");
+        match root {
+            "joined" => {
+                let joined: Joined = from_str(xml_str)
                     .map_err(|_| ProtocolError::ParseError)?;
                 Ok(ProtocolPacket::Joined(joined))
             }
-            s if s.contains("joinPrepared") => {
-                let join_prepared: JoinPrepared = from_str(&xml_str)
+            "joinPrepared" => {
+                let join_prepared: JoinPrepared = from_str(xml_str)
                     .map_err(|_| ProtocolError::ParseError)?;
                 Ok(ProtocolPacket::JoinPrepared(join_prepared))
             }
-            s if s.contains("join") => {
-                let join: Join = from_str(&xml_str)
+            "joinRoom" => {
+                let join_room: JoinRoom = from_str(xml_str)
+                    .map_err(|_| ProtocolError::ParseError)?;
+                Ok(ProtocolPacket::JoinRoom(join_room))
+            }
+            "join" => {
+                let join: Join = from_str(xml_str)
                     .map_err(|_| ProtocolError::ParseError)?;
                 Ok(ProtocolPacket::Join(join))
             }
-            s if s.contains("room") => {
-                let room: Room = from_str(&xml_str)
+            "room" => {
+                let room: Room = from_str(xml_str)
                     .map_err(|_| ProtocolError::ParseError)?;
                 Ok(ProtocolPacket::Room(room))
             }
-            s if s.contains("left") => {
-                let left: Left = from_str(&xml_str)
+            "left" => {
+                let left: Left = from_str(xml_str)
                     .map_err(|_| ProtocolError::ParseError)?;
                 Ok(ProtocolPacket::Left(left))
             }
-            s if s.contains("errorpacket") => {
-                let error_packet: ErrorPacket = from_str(&xml_str)
+            "errorpacket" => {
+                let error_packet: ErrorPacket = from_str(xml_str)
                     .map_err(|_| ProtocolError::ParseError)?;
                 Ok(ProtocolPacket::ErrorPacket(error_packet))
             }
-            s if s.contains("close") => {
-                let close: Close = from_str(&xml_str)
+            "close" => {
+                let close: Close = from_str(xml_str)
                     .map_err(|_| ProtocolError::ParseError)?;
                 Ok(ProtocolPacket::Close(close))
             }
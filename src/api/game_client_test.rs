@@ -1,14 +1,109 @@
 #[cfg(test)]
 mod tests {
-    use crate::api::protocol::{Move, ProtocolPacket, DataClass, Coordinate, Data};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    use crate::api::game_client::{ClientInterface, GameClient, GameContext};
+    use crate::api::mock_server::{MockServer, Step};
+    use crate::api::protocol::{Move, ProtocolPacket, DataClass, Coordinate, Data, OriginalRequest, Team, ErrorPacket, Joined, Left, Room};
+    use crate::api::result::GameResult;
+    use crate::search::time_manager::TimeConfig;
+
+    const WELCOME: &str = r#"<room roomId="r"><data class="welcomeMessage" color="TWO"/></room>"#;
+    const MEMENTO: &str = r#"<room roomId="r"><data class="memento"><state class="state" startTeam="ONE" turn="1" currentTeam="TWO"><board nextDirection="RIGHT"/></state></data></room>"#;
+    const MOVE_REQUEST: &str = r#"<room roomId="r"><data class="moveRequest"/></room>"#;
+    const RESULT: &str = r#"<room roomId="r"><data class="result"><definition><fragment name="Siegpunkte"><aggregation>SUM</aggregation><relevantForRanking>true</relevantForRanking></fragment></definition><scores><entry><player name="a" team="ONE"/><score cause="REGULAR" reason=""><part>0</part></score></entry><entry><player name="b" team="TWO"/><score cause="REGULAR" reason=""><part>2</part></score></entry></scores><winner team="TWO"/></data></room>"#;
+    const LEFT: &str = r#"<left roomId="r"/>"#;
+
+    /// Writes every callback into a shared log and answers move requests with a fixed move.
+    #[derive(Clone, Default)]
+    struct Recording {
+        events: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl Recording {
+        fn push(&self, event: String) {
+            self.events.lock().unwrap().push(event);
+        }
+
+        fn events(&self) -> Vec<String> {
+            self.events.lock().unwrap().clone()
+        }
+    }
+
+    impl ClientInterface for Recording {
+        fn on_state(&mut self, state: Room, _context: &GameContext) {
+            self.push(format!("state {}", state.data.state.unwrap().turn));
+        }
+
+        fn on_move_request(&mut self, _context: &GameContext) -> Move {
+            self.push("move request".to_string());
+            Move { from: Coordinate::new(0, 0), to: Coordinate::new(1, 0) }
+        }
+
+        fn on_error_packet(&mut self, error_packet: ErrorPacket) {
+            self.push(format!("error {}", error_packet.message));
+        }
+
+        fn on_erroneous_move(&mut self, _error: Room, _context: &GameContext) {
+            self.push("erroneous move".to_string());
+        }
+
+        fn on_joined(&mut self, joined: Joined) {
+            self.push(format!("joined {}", joined.room_id));
+        }
+
+        fn on_welcome(&mut self, _welcome_message: Room, context: &GameContext) {
+            self.push(format!("welcome {}", context.my_team().unwrap()));
+        }
+
+        fn on_left(&mut self, left: Left) {
+            self.push(format!("left {}", left.room_id));
+        }
+
+        fn on_result(&mut self, result: Room, _context: &GameContext) {
+            let winner = GameResult::from_data(&result.data).unwrap().winner.unwrap();
+            self.push(format!("result {}", winner));
+        }
+
+        fn on_ponder_start(&mut self) {
+            self.push("ponder start".to_string());
+        }
+
+        fn on_ponder_stop(&mut self) {
+            self.push("ponder stop".to_string());
+        }
+    }
+
+    fn client(server: &MockServer, reservation_code: Option<&str>, room_id: Option<&str>, recording: &Recording) -> GameClient {
+        GameClient::new(
+            "127.0.0.1".to_string(),
+            server.port(),
+            reservation_code.map(str::to_string),
+            room_id.map(str::to_string),
+            Box::new(recording.clone()),
+        )
+        .unwrap()
+    }
+
+    fn memento(turn: i32, current_team: &str) -> Step {
+        send(&format!(
+            r#"<room roomId="r"><data class="memento"><state class="state" startTeam="ONE" turn="{}" currentTeam="{}"><board nextDirection="RIGHT"/></state></data></room>"#,
+            turn, current_team
+        ))
+    }
+
+    fn send(packet: &str) -> Step {
+        Step::Send(format!("{}\n", packet))
+    }
 
     
 
     #[test]
     fn test_new_move() {
         let m: Move = Move {
-            from: Coordinate::from(Coordinate { q: 0, r: 0, s: 0 }),
-            to: Coordinate::from(Coordinate {q: 0, r: 0, s: 0 }),
+            from: Coordinate { q: 0, r: 0, s: 0 },
+            to: Coordinate {q: 0, r: 0, s: 0 },
         };
         let data: Data = Data::new_move(m);
         assert_eq!(data.class, DataClass::Move);
@@ -21,6 +116,16 @@ mod tests {
         assert_eq!(data.winner, None);
     }
 
+    #[test]
+    fn test_welcome_team() {
+        let packet = ProtocolPacket::parse(r#"<room roomId="abc"><data class="welcomeMessage" color="TWO"/></room>"#).unwrap();
+        match packet {
+            ProtocolPacket::Room(room) => assert_eq!(room.data.team(), Some(Team::Two)),
+            _ => panic!("Expected Room packet"),
+        }
+        assert_eq!(Data::new_move(Move::default()).team(), None);
+    }
+
     #[test]
     fn test_join() {
         let xml_str: &str = r#"<join/>"#;
@@ -78,16 +183,39 @@ mod tests {
 
     #[test]
     fn test_message() {
-        let xml_str: &str = r#"<errorpacket><message>Invalid move</message></errorpacket>"#;
+        let xml_str: &str = r#"<errorpacket message="Unknown reservation code"><originalRequest class="joinPrepared" reservationCode="1234"/></errorpacket>"#;
         let packet: ProtocolPacket = ProtocolPacket::parse(xml_str).unwrap();
         match packet {
             ProtocolPacket::ErrorPacket(error_packet) => {
-                assert_eq!(error_packet.message, "Invalid move");
+                assert_eq!(error_packet.message, "Unknown reservation code");
+                assert_eq!(
+                    error_packet.original_request,
+                    Some(OriginalRequest::JoinPrepared { reservation_code: "1234".to_string() })
+                );
             }
             _ => panic!("Expected ErrorPacket packet"),
         }
     }
 
+    #[test]
+    fn test_message_original_requests() {
+        let parse = |request: &str| match ProtocolPacket::parse(&format!(r#"<errorpacket message="m">{}</errorpacket>"#, request)).unwrap() {
+            ProtocolPacket::ErrorPacket(error_packet) => error_packet.original_request,
+            _ => panic!("Expected ErrorPacket packet"),
+        };
+        assert_eq!(parse(r#"<originalRequest class="join"/>"#), Some(OriginalRequest::Join));
+        assert_eq!(
+            parse(r#"<originalRequest class="joinRoom" roomId="abc"/>"#),
+            Some(OriginalRequest::JoinRoom { room_id: "abc".to_string() })
+        );
+        assert_eq!(
+            parse(r#"<originalRequest class="move"><from q="0" r="0" s="0"/><to q="1" r="0" s="-1"/></originalRequest>"#),
+            Some(OriginalRequest::Move(Move { from: Coordinate::new(0, 0), to: Coordinate::new(1, 0) }))
+        );
+        assert_eq!(parse(r#"<originalRequest class="observe"/>"#), Some(OriginalRequest::Other("observe".to_string())));
+        assert_eq!(parse(""), None);
+    }
+
     #[test]
     fn test_close() {
         let xml_str: &str = r#"<close/>"#;
@@ -105,4 +233,122 @@ mod tests {
         assert!(result.is_err());
 
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_run_game() {
+        let server = MockServer::start(vec![
+            Step::Receive,
+            send(r#"<protocol>
+  <joined roomId="r"/>"#),
+            send(WELCOME),
+            send(MEMENTO),
+            send(MOVE_REQUEST),
+            Step::Receive,
+            send(RESULT),
+            send(LEFT),
+        ])
+        .unwrap();
+        let recording = Recording::default();
+        let mut client = client(&server, None, None, &recording);
+        client.run().unwrap();
+
+        assert_eq!(
+            server.finish().unwrap(),
+            vec![
+                "<join/>".to_string(),
+                r#"<room roomId="r"><data class="move"><from q="0" r="0" s="0" /><to q="1" r="0" s="-1" /></data></room>"#.to_string(),
+            ]
+        );
+        assert_eq!(
+            recording.events(),
+            vec!["joined r", "welcome TWO", "state 1", "move request", "result TWO", "left r"]
+        );
+        assert_eq!(client.my_team(), Some(Team::Two));
+        assert_eq!(client.opponent_team(), Some(Team::One));
+    }
+
+    #[test]
+    fn test_join_with_reservation_code() {
+        let server = MockServer::start(vec![Step::Receive, send(LEFT)]).unwrap();
+        client(&server, Some("abc"), Some("ignored"), &Recording::default()).run().unwrap();
+        assert_eq!(server.finish().unwrap(), vec![r#"<joinPrepared reservationCode="abc" />"#]);
+    }
+
+    #[test]
+    fn test_join_room() {
+        let server = MockServer::start(vec![Step::Receive, send(LEFT)]).unwrap();
+        client(&server, None, Some("r"), &Recording::default()).run().unwrap();
+        assert_eq!(server.finish().unwrap(), vec![r#"<joinRoom roomId="r" />"#]);
+    }
+
+    #[test]
+    fn test_error_packet_and_closed_connection() {
+        let server = MockServer::start(vec![
+            Step::Receive,
+            send(r#"<protocol><errorpacket message="Unknown reservation code"><originalRequest class="joinPrepared" reservationCode="abc"/></errorpacket>"#),
+        ])
+        .unwrap();
+        let recording = Recording::default();
+        let mut client = client(&server, Some("abc"), None, &recording);
+        client.run().unwrap();
+        server.finish().unwrap();
+        assert_eq!(recording.events(), vec!["error Unknown reservation code"]);
+        assert_eq!(client.my_team(), None);
+    }
+
+    #[test]
+    fn test_invalid_packet() {
+        let server = MockServer::start(vec![Step::Receive, send("<invalid/>")]).unwrap();
+        assert!(client(&server, None, None, &Recording::default()).run().is_err());
+        server.finish().unwrap();
+    }
+
+    #[test]
+    fn test_ponder_until_opponent_moved() {
+        let server = MockServer::start(vec![
+            Step::Receive,
+            send(WELCOME),
+            send(MOVE_REQUEST),
+            Step::Receive,
+            // The memento of the own move arrives first, the opponent is still thinking.
+            memento(2, "ONE"),
+            memento(3, "TWO"),
+            send(MOVE_REQUEST),
+            Step::Receive,
+        ])
+        .unwrap();
+        let recording = Recording::default();
+        let mut client = client(&server, None, None, &recording);
+        client.set_ponder(true);
+        client.run().unwrap();
+        server.finish().unwrap();
+
+        assert_eq!(
+            recording.events(),
+            vec![
+                "welcome TWO", "move request", "ponder start", "state 2", "ponder stop", "state 3",
+                "move request", "ponder start", "ponder stop",
+            ]
+        );
+    }
+    #[test]
+    fn test_round_trip_reaches_time_manager() {
+        let server = MockServer::start(vec![
+            Step::Receive,
+            send(WELCOME),
+            send(MOVE_REQUEST),
+            Step::Receive,
+            Step::Wait(Duration::from_millis(50)),
+            memento(2, "ONE"),
+            send(LEFT),
+        ])
+        .unwrap();
+        let mut client = client(&server, None, None, &Recording::default());
+        client.set_time_config(TimeConfig { min_margin: Duration::from_millis(10), ..Default::default() });
+        assert_eq!(client.context().time_manager.safety_margin(), Duration::from_millis(10));
+        client.run().unwrap();
+        server.finish().unwrap();
+
+        assert!(client.context().time_manager.safety_margin() >= Duration::from_millis(60));
+    }
+}
@@ -0,0 +1,240 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::fmt;
+
+use crate::api::board::{SEGMENT_COLUMNS, SEGMENT_ROWS};
+use crate::api::protocol::{Board, Coordinate, CubeDirection, Field, FieldArray, Passenger, Segment};
+use crate::random::Random;
+
+/// Smallest board: the ships start on the first segment and the goal is on the last one.
+pub const MIN_SEGMENTS: usize = 2;
+
+/// Limits for [`Board::generate_with`]. The defaults are the values of the official game.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoardConfig {
+    /// At least [`MIN_SEGMENTS`].
+    pub segments: usize,
+    /// Number of passenger docks. Each one is placed on a different segment after the first.
+    pub passengers: usize,
+    pub min_islands: usize,
+    pub max_islands: usize,
+    pub min_sandbanks: usize,
+    pub max_sandbanks: usize,
+    /// Largest number of turns a segment direction may deviate from `Right`, at most 2.
+    pub max_deviation: i32,
+}
+
+/// Why [`Board::generate_with`] rejected a [`BoardConfig`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum BoardConfigError {
+    /// Fewer than [`MIN_SEGMENTS`] segments, so the board would have no goal.
+    TooFewSegments(usize),
+    /// A minimum above its maximum, with the name of the range.
+    EmptyRange(&'static str, usize, usize),
+    NegativeDeviation(i32),
+    /// No layout of the requested segments avoids overlapping ones.
+    NoLayout,
+}
+
+impl Error for BoardConfigError {}
+
+impl fmt::Display for BoardConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BoardConfigError::TooFewSegments(segments) => {
+                write!(f, "A board needs at least {} segments to have a goal, got {}", MIN_SEGMENTS, segments)
+            }
+            BoardConfigError::EmptyRange(name, min, max) => write!(f, "Minimum {} of {} is above the maximum {}", min, name, max),
+            BoardConfigError::NegativeDeviation(deviation) => write!(f, "Negative maximum deviation: {}", deviation),
+            BoardConfigError::NoLayout => write!(f, "The segments do not fit on the board without overlapping"),
+        }
+    }
+}
+
+impl BoardConfig {
+    pub fn validate(&self) -> Result<(), BoardConfigError> {
+        if self.segments < MIN_SEGMENTS {
+            return Err(BoardConfigError::TooFewSegments(self.segments));
+        }
+        if self.min_islands > self.max_islands {
+            return Err(BoardConfigError::EmptyRange("islands", self.min_islands, self.max_islands));
+        }
+        if self.min_sandbanks > self.max_sandbanks {
+            return Err(BoardConfigError::EmptyRange("sandbanks", self.min_sandbanks, self.max_sandbanks));
+        }
+        if self.max_deviation < 0 {
+            return Err(BoardConfigError::NegativeDeviation(self.max_deviation));
+        }
+        Ok(())
+    }
+}
+
+impl Default for BoardConfig {
+    fn default() -> Self {
+        BoardConfig {
+            segments: 8,
+            passengers: 5,
+            min_islands: 2,
+            max_islands: 3,
+            min_sandbanks: 0,
+            max_sandbanks: 1,
+            max_deviation: 2,
+        }
+    }
+}
+
+impl Board {
+    /// Generates a full board with the default [`BoardConfig`]. Equal seeds give equal boards.
+    pub fn generate(seed: u64) -> Board {
+        Board::generate_with(&BoardConfig::default(), seed).expect("the default config is valid")
+    }
+
+    /// Generates a full board following the placement rules of the game server:
+    ///
+    /// - The first segment points `Right` and only has water, the ships start there.
+    /// - Every further segment turns at most one step against its predecessor, stays within
+    ///   `max_deviation` turns of `Right` and never overlaps an earlier segment. If a segment
+    ///   cannot be placed, the generator goes back and turns an earlier one differently.
+    /// - Segments after the first get islands and sandbanks in the configured ranges, and
+    ///   `passengers` of them get a dock with one passenger whose landing field is water.
+    /// - Nothing is placed on the current, and the last column of the last segment is the goal.
+    ///
+    /// Fails if the config is invalid, see [`BoardConfig::validate`].
+    pub fn generate_with(config: &BoardConfig, seed: u64) -> Result<Board, BoardConfigError> {
+        config.validate()?;
+        let mut random = Random::new(seed);
+        let layout = segment_layout(config, &mut random).ok_or(BoardConfigError::NoLayout)?;
+
+        let mut dock_segments: Vec<usize> = (1..layout.len()).collect();
+        shuffle(&mut dock_segments, &mut random);
+        dock_segments.truncate(config.passengers);
+
+        let mut board = Board {
+            next_direction: layout.last().map_or(CubeDirection::Right, |(direction, _)| direction.clone()),
+            segment: layout
+                .iter()
+                .map(|(direction, center)| Segment {
+                    direction: direction.clone(),
+                    center: center.clone(),
+                    field_array: (0..SEGMENT_COLUMNS).map(|_| FieldArray { fields: vec![Field::Water; SEGMENT_ROWS] }).collect(),
+                })
+                .collect(),
+        };
+
+        let last = board.segment.len().saturating_sub(1);
+        for index in 1..board.segment.len() {
+            let mut free: Vec<(usize, usize)> = (0..SEGMENT_COLUMNS)
+                .flat_map(|column| (0..SEGMENT_ROWS).map(move |row| (column, row)))
+                .filter(|&(column, row)| {
+                    let goal = index == last && column == SEGMENT_COLUMNS - 1;
+                    !goal && !board.has_current(&board.segment[index].coordinate_of(column, row))
+                })
+                .collect();
+
+            if dock_segments.contains(&index) {
+                place_dock(&mut board.segment[index], &mut free, &mut random);
+            }
+            let islands = between(config.min_islands, config.max_islands, &mut random);
+            place(&mut board.segment[index], &mut free, islands, Field::Island, &mut random);
+            let sandbanks = between(config.min_sandbanks, config.max_sandbanks, &mut random);
+            place(&mut board.segment[index], &mut free, sandbanks, Field::Sandbank, &mut random);
+        }
+
+        if let Some(segment) = board.segment.last_mut() {
+            segment.field_array[SEGMENT_COLUMNS - 1].fields.fill(Field::Goal);
+        }
+        Ok(board)
+    }
+}
+
+/// Direction and center of every segment, or `None` if the segments cannot be laid out.
+fn segment_layout(config: &BoardConfig, random: &mut Random) -> Option<Vec<(CubeDirection, Coordinate)>> {
+    let mut layout = vec![(CubeDirection::Right, Coordinate::new(0, 0))];
+    let mut occupied: HashSet<Coordinate> = segment_coordinates(&CubeDirection::Right, &Coordinate::new(0, 0)).collect();
+    extend_layout(config, random, 0, &mut layout, &mut occupied).then_some(layout)
+}
+
+/// Appends segments until there are `config.segments`. The free candidates are tried in random
+/// order, and a candidate is taken back if no layout of the remaining segments follows it.
+fn extend_layout(
+    config: &BoardConfig,
+    random: &mut Random,
+    turns: i32,
+    layout: &mut Vec<(CubeDirection, Coordinate)>,
+    occupied: &mut HashSet<Coordinate>,
+) -> bool {
+    if layout.len() >= config.segments {
+        return true;
+    }
+    let (_, center) = layout.last().unwrap().clone();
+    let mut candidates: Vec<(i32, CubeDirection, Coordinate)> = [turns - 1, turns, turns + 1]
+        .into_iter()
+        .filter(|candidate: &i32| candidate.abs() <= config.max_deviation.min(2))
+        .filter_map(|candidate| {
+            let direction = CubeDirection::HEX[candidate.rem_euclid(6) as usize].clone();
+            let next_center = center.clone() + direction.vector()? * SEGMENT_COLUMNS as i32;
+            let free = segment_coordinates(&direction, &next_center).all(|coordinate| !occupied.contains(&coordinate));
+            free.then_some((candidate, direction, next_center))
+        })
+        .collect();
+
+    while !candidates.is_empty() {
+        let (candidate, direction, next_center) = candidates.swap_remove(random.below(candidates.len()));
+        let coordinates: Vec<Coordinate> = segment_coordinates(&direction, &next_center).collect();
+        occupied.extend(coordinates.iter().cloned());
+        layout.push((direction, next_center));
+        if extend_layout(config, random, candidate, layout, occupied) {
+            return true;
+        }
+        layout.pop();
+        for coordinate in &coordinates {
+            occupied.remove(coordinate);
+        }
+    }
+    false
+}
+
+fn segment_coordinates(direction: &CubeDirection, center: &Coordinate) -> impl Iterator<Item = Coordinate> {
+    let segment = Segment { direction: direction.clone(), center: center.clone(), field_array: Vec::new() };
+    (0..SEGMENT_COLUMNS).flat_map(move |column| {
+        let segment = segment.clone();
+        (0..SEGMENT_ROWS).map(move |row| segment.coordinate_of(column, row))
+    })
+}
+
+/// Puts a dock on a free field whose neighbour in the dock direction is a free field of the
+/// same segment, and keeps that landing field free of obstacles.
+fn place_dock(segment: &mut Segment, free: &mut Vec<(usize, usize)>, random: &mut Random) {
+    let mut options = Vec::new();
+    for &(column, row) in free.iter() {
+        for direction in CubeDirection::HEX {
+            let landing = segment.coordinate_of(column, row) + direction.vector().unwrap();
+            if let Some(index) = segment.index_of(&landing).filter(|index| free.contains(index)) {
+                options.push(((column, row), direction, index));
+            }
+        }
+    }
+    if options.is_empty() {
+        return;
+    }
+    let ((column, row), direction, landing) = options.swap_remove(random.below(options.len()));
+    segment.field_array[column].fields[row] = Field::Passenger(Passenger { direction, passenger: 1 });
+    free.retain(|&index| index != (column, row) && index != landing);
+}
+
+fn place(segment: &mut Segment, free: &mut Vec<(usize, usize)>, count: usize, field: Field, random: &mut Random) {
+    for _ in 0..count.min(free.len()) {
+        let (column, row) = free.swap_remove(random.below(free.len()));
+        segment.field_array[column].fields[row] = field.clone();
+    }
+}
+
+fn between(min: usize, max: usize, random: &mut Random) -> usize {
+    min + random.below(max.saturating_sub(min) + 1)
+}
+
+fn shuffle<T>(items: &mut [T], random: &mut Random) {
+    for i in (1..items.len()).rev() {
+        items.swap(i, random.below(i + 1));
+    }
+}
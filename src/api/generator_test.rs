@@ -0,0 +1,116 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use crate::api::board::SEGMENT_COLUMNS;
+    use crate::api::generator::{BoardConfig, BoardConfigError, MIN_SEGMENTS};
+    use crate::api::protocol::{Board, CubeDirection, Field};
+
+    fn count(board: &Board, index: usize, predicate: impl Fn(&Field) -> bool) -> usize {
+        board.segment[index].fields().filter(|(_, field)| predicate(field)).count()
+    }
+
+    #[test]
+    fn test_same_seed_same_board() {
+        assert_eq!(Board::generate(7), Board::generate(7));
+        assert_ne!(Board::generate(7), Board::generate(8));
+    }
+
+    #[test]
+    fn test_layout() {
+        for seed in 0..200 {
+            let board = Board::generate(seed);
+            assert_eq!(board.segment.len(), 8);
+            assert_eq!(board.segment[0].direction, CubeDirection::Right);
+            assert_eq!(board.next_direction, board.segment[7].direction);
+
+            for pair in board.segment.windows(2) {
+                let step = (pair[1].direction.turns().unwrap() - pair[0].direction.turns().unwrap()).rem_euclid(6);
+                assert!([0, 1, 5].contains(&step), "seed {}: segments turn by {} steps", seed, step);
+                assert_ne!(pair[1].direction, CubeDirection::Left);
+            }
+
+            let coordinates: HashSet<_> = board.fields().map(|(coordinate, _)| coordinate).collect();
+            assert_eq!(coordinates.len(), 8 * 20, "seed {}: segments overlap", seed);
+        }
+    }
+
+    #[test]
+    fn test_fields() {
+        for seed in 0..200 {
+            let board = Board::generate(seed);
+            assert_eq!(count(&board, 0, |field| *field != Field::Water), 0);
+
+            let mut docks = 0;
+            for index in 1..8 {
+                let islands = count(&board, index, |field| *field == Field::Island);
+                assert!((2..=3).contains(&islands), "seed {}: {} islands", seed, islands);
+                assert!(count(&board, index, |field| *field == Field::Sandbank) <= 1);
+                let segment_docks = count(&board, index, |field| matches!(field, Field::Passenger(_)));
+                assert!(segment_docks <= 1);
+                docks += segment_docks;
+            }
+            assert_eq!(docks, 5);
+
+            for (coordinate, field) in board.fields() {
+                if board.has_current(&coordinate) {
+                    assert!(matches!(field, Field::Water | Field::Goal), "seed {}: {:?} on the current", seed, field);
+                }
+                if let Field::Passenger(passenger) = field {
+                    assert_eq!(passenger.passenger, 1);
+                    let landing = coordinate.clone() + passenger.direction.vector().unwrap();
+                    assert_eq!(board.segment_index(&landing), board.segment_index(&coordinate));
+                    assert_eq!(board.field_at(&landing), Some(&Field::Water));
+                }
+            }
+
+            let goal = &board.segment[7].field_array[3];
+            assert!(goal.fields.iter().all(|field| *field == Field::Goal));
+            assert_eq!(board.fields().filter(|(_, field)| *field == &Field::Goal).count(), 5);
+        }
+    }
+
+    #[test]
+    fn test_config() {
+        let config = BoardConfig { segments: 3, passengers: 0, min_islands: 0, max_islands: 0, max_deviation: 0, ..Default::default() };
+        let board = Board::generate_with(&config, 1).unwrap();
+        assert_eq!(board.segment.len(), 3);
+        assert!(board.segment.iter().all(|segment| segment.direction == CubeDirection::Right));
+        assert!(board.fields().all(|(_, field)| matches!(field, Field::Water | Field::Sandbank | Field::Goal)));
+    }
+
+    #[test]
+    fn test_smallest_board() {
+        let config = BoardConfig { segments: MIN_SEGMENTS, ..Default::default() };
+        let board = Board::generate_with(&config, 1).unwrap();
+        assert_eq!(board.segment.len(), 2);
+        assert!(board.segment[1].field_array[SEGMENT_COLUMNS - 1].fields.iter().all(|field| *field == Field::Goal));
+    }
+
+    #[test]
+    fn test_long_boards_do_not_overlap() {
+        for seed in 0..50 {
+            let board = Board::generate_with(&BoardConfig { segments: 40, ..Default::default() }, seed).unwrap();
+            let coordinates: HashSet<_> = board.fields().map(|(coordinate, _)| coordinate).collect();
+            assert_eq!(coordinates.len(), 40 * 20, "seed {}: segments overlap", seed);
+        }
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        let generate = |config: BoardConfig| Board::generate_with(&config, 1);
+        assert_eq!(
+            generate(BoardConfig { segments: 1, passengers: 0, ..Default::default() }),
+            Err(BoardConfigError::TooFewSegments(1))
+        );
+        assert_eq!(
+            generate(BoardConfig { min_islands: 4, ..Default::default() }),
+            Err(BoardConfigError::EmptyRange("islands", 4, 3))
+        );
+        assert_eq!(
+            generate(BoardConfig { min_sandbanks: 2, ..Default::default() }),
+            Err(BoardConfigError::EmptyRange("sandbanks", 2, 1))
+        );
+        assert_eq!(generate(BoardConfig { max_deviation: -1, ..Default::default() }), Err(BoardConfigError::NegativeDeviation(-1)));
+    }
+}
@@ -0,0 +1,84 @@
+use std::io::{self, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use crate::api::xml_buffer::XmlBuffer;
+
+/// How long the server waits for the client before the test fails.
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// One step of the conversation with the client.
+#[derive(Clone, Debug)]
+pub enum Step {
+    /// Writes the text to the client as is.
+    Send(String),
+    /// Waits for the next packet of the client and records it.
+    Receive,
+    /// Pauses the script, e.g. to simulate a slow connection.
+    Wait(Duration),
+}
+
+/// A stand-in for the game server that accepts one client on localhost and plays a script.
+///
+/// The client has to open the stream with `<protocol>`. The packets received by
+/// [`Step::Receive`] are returned by [`MockServer::finish`], so a test can check what the client
+/// sent after the client has finished.
+pub struct MockServer {
+    port: u16,
+    handle: JoinHandle<io::Result<Vec<String>>>,
+}
+
+impl MockServer {
+    pub fn start(script: Vec<Step>) -> io::Result<MockServer> {
+        let listener = TcpListener::bind(("127.0.0.1", 0))?;
+        let port = listener.local_addr()?.port();
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept()?;
+            play(stream, script)
+        });
+        Ok(MockServer { port, handle })
+    }
+
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// Waits until the script is done and returns the received packets.
+    pub fn finish(self) -> io::Result<Vec<String>> {
+        self.handle.join().map_err(|_| io::Error::other("mock server panicked"))?
+    }
+}
+
+fn play(mut stream: TcpStream, script: Vec<Step>) -> io::Result<Vec<String>> {
+    stream.set_read_timeout(Some(TIMEOUT))?;
+    let mut opening = [0; 10];
+    stream.read_exact(&mut opening)?;
+    if &opening != b"<protocol>" {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "client did not open the stream with <protocol>"));
+    }
+    let mut buffer = XmlBuffer::new();
+    let mut received = Vec::new();
+    for step in script {
+        match step {
+            Step::Send(packet) => stream.write_all(packet.as_bytes())?,
+            Step::Receive => received.push(receive(&mut stream, &mut buffer)?),
+            Step::Wait(duration) => thread::sleep(duration),
+        }
+    }
+    Ok(received)
+}
+
+fn receive(stream: &mut TcpStream, buffer: &mut XmlBuffer) -> io::Result<String> {
+    loop {
+        if let Some(packet) = buffer.pop_xml() {
+            return Ok(String::from_utf8_lossy(&packet).into_owned());
+        }
+        let mut buf = [0; 4096];
+        let bytes_read = stream.read(&mut buf)?;
+        if bytes_read == 0 {
+            return Err(io::Error::from(io::ErrorKind::UnexpectedEof));
+        }
+        buffer.push(&buf[..bytes_read]);
+    }
+}
@@ -0,0 +1,322 @@
+//! Compact single-line notation for a [`State`], mainly for test fixtures and log lines.
+//!
+//! ```text
+//! <start team> <current team> <turn> <next direction> <board> <ship> <ship>
+//! 1 1 0 DR R@0,0:wwwww|wwwww|wwwww|wwwww 1R@-1,-1:1,6,0,1,0 2R@-2,1:1,6,0,1,0
+//! ```
+//!
+//! * Teams are `1` and `2`.
+//! * Directions are `R`, `DR`, `DL`, `L`, `UL`, `UR` (and `U`, `D`).
+//! * The board is a `/`-separated list of segments, or `-` if there are none. A segment is
+//!   `<direction>@<q>,<r>:` followed by its field arrays separated by `|`. A segment without
+//!   field arrays is written without the `:`.
+//! * Fields are `w` water, `i` island, `s` sandbank, `g` goal and `p<direction><passengers>` for a passenger dock.
+//! * A ship is `<team><direction>@<q>,<r>:<speed>,<coal>,<passengers>,<free turns>,<points>`.
+//!
+//! The `s` component of coordinates is only written, as `<q>,<r>,<s>`, if it differs from
+//! `-q - r`; otherwise it is restored as `-q - r`.
+//!
+//! The `class` attribute of the state is not part of the notation. `from_notation` always sets
+//! it to `"state"`, the only class the server sends.
+
+use std::error::Error;
+use std::fmt;
+
+use crate::api::protocol::{Board, Coordinate, CubeDirection, Field, FieldArray, Passenger, Segment, Ship, State, Team};
+
+impl State {
+    pub fn to_notation(&self) -> String {
+        let mut parts = vec![
+            team_notation(&self.start_team).to_string(),
+            team_notation(&self.current_team).to_string(),
+            self.turn.to_string(),
+            direction_notation(&self.board.next_direction).to_string(),
+        ];
+
+        if self.board.segment.is_empty() {
+            parts.push("-".to_string());
+        } else {
+            let segments: Vec<String> = self.board.segment.iter().map(segment_notation).collect();
+            parts.push(segments.join("/"));
+        }
+
+        parts.extend(self.ship.iter().map(ship_notation));
+        parts.join(" ")
+    }
+
+    pub fn from_notation(notation: &str) -> Result<State, NotationError> {
+        let mut parser = Parser { input: notation.chars().collect(), position: 0 };
+
+        let start_team = parser.team()?;
+        parser.expect(' ')?;
+        let current_team = parser.team()?;
+        parser.expect(' ')?;
+        let turn = parser.number()?;
+        parser.expect(' ')?;
+        let next_direction = parser.direction()?;
+        parser.expect(' ')?;
+
+        let mut segment = Vec::new();
+        if !parser.accept('-') {
+            segment.push(parser.segment()?);
+            while parser.accept('/') {
+                segment.push(parser.segment()?);
+            }
+        }
+
+        let mut ship = Vec::new();
+        while parser.accept(' ') {
+            ship.push(parser.ship()?);
+        }
+        parser.end()?;
+
+        Ok(State {
+            class: "state".to_string(),
+            start_team,
+            turn,
+            current_team,
+            board: Board { next_direction, segment },
+            ship,
+        })
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct NotationError {
+    /// 1-based column of the character where parsing failed.
+    pub column: usize,
+    pub message: String,
+}
+
+impl Error for NotationError {}
+
+impl fmt::Display for NotationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Invalid state notation at column {}: {}", self.column, self.message)
+    }
+}
+
+fn team_notation(team: &Team) -> char {
+    match team {
+        Team::One => '1',
+        Team::Two => '2',
+    }
+}
+
+fn direction_notation(direction: &CubeDirection) -> &'static str {
+    match direction {
+        CubeDirection::Right => "R",
+        CubeDirection::UpRight => "UR",
+        CubeDirection::Up => "U",
+        CubeDirection::UpLeft => "UL",
+        CubeDirection::Left => "L",
+        CubeDirection::DownLeft => "DL",
+        CubeDirection::Down => "D",
+        CubeDirection::DownRight => "DR",
+    }
+}
+
+fn segment_notation(segment: &Segment) -> String {
+    let columns: Vec<String> = segment
+        .field_array
+        .iter()
+        .map(|column| {
+            column
+                .fields
+                .iter()
+                .map(|field| match field {
+                    Field::Water => "w".to_string(),
+                    Field::Island => "i".to_string(),
+                    Field::Sandbank => "s".to_string(),
+                    Field::Goal => "g".to_string(),
+                    Field::Passenger(passenger) => {
+                        format!("p{}{}", direction_notation(&passenger.direction), passenger.passenger)
+                    }
+                })
+                .collect()
+        })
+        .collect();
+    if columns.is_empty() {
+        format!("{}@{}", direction_notation(&segment.direction), coordinate_notation(&segment.center))
+    } else {
+        format!("{}@{}:{}", direction_notation(&segment.direction), coordinate_notation(&segment.center), columns.join("|"))
+    }
+}
+
+fn coordinate_notation(coordinate: &Coordinate) -> String {
+    if coordinate.s == -coordinate.q - coordinate.r {
+        format!("{},{}", coordinate.q, coordinate.r)
+    } else {
+        format!("{},{},{}", coordinate.q, coordinate.r, coordinate.s)
+    }
+}
+
+fn ship_notation(ship: &Ship) -> String {
+    format!(
+        "{}{}@{}:{},{},{},{},{}",
+        team_notation(&ship.team),
+        direction_notation(&ship.direction),
+        coordinate_notation(&ship.position),
+        ship.speed,
+        ship.coal,
+        ship.passengers,
+        ship.free_turns,
+        ship.points
+    )
+}
+
+struct Parser {
+    input: Vec<char>,
+    position: usize,
+}
+
+impl Parser {
+    fn error<T>(&self, message: impl Into<String>) -> Result<T, NotationError> {
+        Err(NotationError { column: self.position + 1, message: message.into() })
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.input.get(self.position).copied()
+    }
+
+    fn accept(&mut self, expected: char) -> bool {
+        if self.peek() == Some(expected) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), NotationError> {
+        match self.peek() {
+            Some(c) if c == expected => {
+                self.position += 1;
+                Ok(())
+            }
+            Some(c) => self.error(format!("expected '{}', found '{}'", expected, c)),
+            None => self.error(format!("expected '{}', found end of input", expected)),
+        }
+    }
+
+    fn end(&self) -> Result<(), NotationError> {
+        match self.peek() {
+            None => Ok(()),
+            Some(c) => self.error(format!("unexpected '{}'", c)),
+        }
+    }
+
+    fn number(&mut self) -> Result<i32, NotationError> {
+        let start = self.position;
+        self.accept('-');
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.position += 1;
+        }
+        let text: String = self.input[start..self.position].iter().collect();
+        text.parse().or_else(|_| {
+            self.position = start;
+            self.error("expected a number")
+        })
+    }
+
+    fn team(&mut self) -> Result<Team, NotationError> {
+        match self.peek() {
+            Some('1') => {
+                self.position += 1;
+                Ok(Team::One)
+            }
+            Some('2') => {
+                self.position += 1;
+                Ok(Team::Two)
+            }
+            _ => self.error("expected team '1' or '2'"),
+        }
+    }
+
+    fn direction(&mut self) -> Result<CubeDirection, NotationError> {
+        let direction = match self.peek() {
+            Some('R') => CubeDirection::Right,
+            Some('L') => CubeDirection::Left,
+            Some('U') => match self.input.get(self.position + 1) {
+                Some('R') => CubeDirection::UpRight,
+                Some('L') => CubeDirection::UpLeft,
+                _ => CubeDirection::Up,
+            },
+            Some('D') => match self.input.get(self.position + 1) {
+                Some('R') => CubeDirection::DownRight,
+                Some('L') => CubeDirection::DownLeft,
+                _ => CubeDirection::Down,
+            },
+            _ => return self.error("expected a direction"),
+        };
+        self.position += direction_notation(&direction).len();
+        Ok(direction)
+    }
+
+    fn coordinate(&mut self) -> Result<Coordinate, NotationError> {
+        let q = self.number()?;
+        self.expect(',')?;
+        let r = self.number()?;
+        let mut coordinate = Coordinate::new(q, r);
+        if self.accept(',') {
+            coordinate.s = self.number()?;
+        }
+        Ok(coordinate)
+    }
+
+    fn segment(&mut self) -> Result<Segment, NotationError> {
+        let direction = self.direction()?;
+        self.expect('@')?;
+        let center = self.coordinate()?;
+
+        let mut field_array = Vec::new();
+        if self.accept(':') {
+            field_array.push(self.field_array()?);
+            while self.accept('|') {
+                field_array.push(self.field_array()?);
+            }
+        }
+        Ok(Segment { direction, center, field_array })
+    }
+
+    fn field_array(&mut self) -> Result<FieldArray, NotationError> {
+        let mut fields = Vec::new();
+        loop {
+            let field = match self.peek() {
+                Some('w') => Field::Water,
+                Some('i') => Field::Island,
+                Some('s') => Field::Sandbank,
+                Some('g') => Field::Goal,
+                Some('p') => {
+                    self.position += 1;
+                    let direction = self.direction()?;
+                    let passenger = self.number()?;
+                    fields.push(Field::Passenger(Passenger { direction, passenger }));
+                    continue;
+                }
+                _ => break,
+            };
+            self.position += 1;
+            fields.push(field);
+        }
+        Ok(FieldArray { fields })
+    }
+
+    fn ship(&mut self) -> Result<Ship, NotationError> {
+        let team = self.team()?;
+        let direction = self.direction()?;
+        self.expect('@')?;
+        let position = self.coordinate()?;
+        self.expect(':')?;
+        let speed = self.number()?;
+        self.expect(',')?;
+        let coal = self.number()?;
+        self.expect(',')?;
+        let passengers = self.number()?;
+        self.expect(',')?;
+        let free_turns = self.number()?;
+        self.expect(',')?;
+        let points = self.number()?;
+        Ok(Ship { team, direction, speed, coal, passengers, free_turns, points, position })
+    }
+}
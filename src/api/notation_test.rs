@@ -0,0 +1,88 @@
+#[cfg(test)]
+mod tests {
+    use crate::api::notation::NotationError;
+    use crate::api::protocol::{Coordinate, CubeDirection, Field, Passenger, State, Team};
+
+    const NOTATION: &str = "1 2 5 DR R@0,0:wwwww|wiwww|pUL1swwg|wwwww/DR@2,4:wwwww|wwwww|wwwww|ggggg 1R@-1,-1:1,6,0,1,0 2DL@3,4:2,5,1,0,-2";
+
+    #[test]
+    fn test_notation_round_trip() {
+        let state = State::from_notation(NOTATION).unwrap();
+        assert_eq!(state.start_team, Team::One);
+        assert_eq!(state.current_team, Team::Two);
+        assert_eq!(state.turn, 5);
+        assert_eq!(state.board.next_direction, CubeDirection::DownRight);
+        assert_eq!(state.board.segment.len(), 2);
+        assert_eq!(
+            state.board.segment[0].field_array[2].fields[0],
+            Field::Passenger(Passenger { direction: CubeDirection::UpLeft, passenger: 1 })
+        );
+        assert_eq!(state.ship[1].position.s, -7);
+        assert_eq!(state.ship[1].points, -2);
+        assert_eq!(state.to_notation(), NOTATION);
+    }
+
+    #[test]
+    fn test_notation_matches_xml() {
+        let xml: &str = r#"
+        <state class="state" startTeam="ONE" turn="0" currentTeam="ONE">
+          <board nextDirection="DOWN_RIGHT">
+            <segment direction="RIGHT">
+              <center q="0" r="0" s="0"/>
+              <field-array><water/><water/><water/><water/><water/></field-array>
+            </segment>
+          </board>
+          <ship team="ONE" direction="RIGHT" speed="1" coal="6" passengers="0" freeTurns="1" points="0">
+            <position q="-1" r="-1" s="2"/>
+          </ship>
+          <ship team="TWO" direction="RIGHT" speed="1" coal="6" passengers="0" freeTurns="1" points="0">
+            <position q="-2" r="1" s="1"/>
+          </ship>
+        </state>
+        "#;
+        let state: State = yaserde::de::from_str(xml).unwrap();
+        let notation = "1 1 0 DR R@0,0:wwwww 1R@-1,-1:1,6,0,1,0 2R@-2,1:1,6,0,1,0";
+        assert_eq!(state.to_notation(), notation);
+        assert_eq!(State::from_notation(notation).unwrap(), state);
+    }
+
+    #[test]
+    fn test_empty_board() {
+        let state = State::from_notation("2 2 10 L -").unwrap();
+        assert!(state.board.segment.is_empty());
+        assert!(state.ship.is_empty());
+        assert_eq!(state.to_notation(), "2 2 10 L -");
+    }
+
+    #[test]
+    fn test_lossy_parts_round_trip() {
+        // A segment without field arrays, an empty field array and a coordinate whose `s` is not `-q - r`.
+        let notation = "1 1 0 R R@0,0/R@4,0:ww||w 1R@-1,-1,5:1,6,0,1,0";
+        let state = State::from_notation(notation).unwrap();
+        assert!(state.board.segment[0].field_array.is_empty());
+        assert_eq!(state.board.segment[1].field_array[1].fields, vec![]);
+        assert_eq!(state.ship[0].position, Coordinate { q: -1, r: -1, s: 5 });
+        assert_eq!(state.to_notation(), notation);
+        assert_eq!(State::from_notation(&state.to_notation()).unwrap(), state);
+
+        // The class is not encoded and always comes back as "state".
+        let mut other = state.clone();
+        other.class = "other".to_string();
+        assert_eq!(State::from_notation(&other.to_notation()).unwrap(), state);
+    }
+
+    #[test]
+    fn test_notation_errors() {
+        assert_eq!(
+            State::from_notation("3 1 0 R -"),
+            Err(NotationError { column: 1, message: "expected team '1' or '2'".to_string() })
+        );
+        assert_eq!(State::from_notation("1 1 x R -").unwrap_err().column, 5);
+        assert_eq!(State::from_notation("1 1 0 R R@0,0:wwxww").unwrap_err().column, 17);
+        assert_eq!(State::from_notation("1 1 0 R - 1R@0,0:1,2,3").unwrap_err().column, 23);
+        assert_eq!(
+            State::from_notation("1 1 0 Q -").unwrap_err().to_string(),
+            "Invalid state notation at column 7: expected a direction"
+        );
+    }
+}
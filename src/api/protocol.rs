@@ -1,19 +1,39 @@
+use std::io::{Read, Write};
+
+use yaserde::__xml::attribute::OwnedAttribute;
+use yaserde::__xml::namespace::Namespace;
+use yaserde::__xml::reader::XmlEvent;
+use yaserde::__xml::writer::XmlEvent as WriterEvent;
+use yaserde::de::Deserializer;
+use yaserde::ser::{Config, Serializer};
+use yaserde::{YaDeserialize, YaSerialize};
 use yaserde_derive::{YaDeserialize, YaSerialize};
 
+/// Serializes a packet the way the server writes it: without an XML declaration and without
+/// indentation, so it can be sent as is inside the open `<protocol>` stream.
+pub fn to_xml<T: YaSerialize>(value: &T) -> Result<String, String> {
+    let config = Config { write_document_declaration: false, ..Default::default() };
+    yaserde::ser::to_string_with_config(value, &config)
+}
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub enum ProtocolPacket {
     Joined(Joined),
     Join(Join),
     Room(Room),
     Left(Left),
     JoinPrepared(JoinPrepared),
+    JoinRoom(JoinRoom),
     ErrorPacket(ErrorPacket),
     Close(Close),
 }
 
 
-#[derive(Clone, Debug, Default, YaDeserialize, YaSerialize)]
+#[derive(Clone, Debug, Default, PartialEq, YaDeserialize, YaSerialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 #[yaserde(rename = "protocol")]
 pub struct Protocol {
     #[yaserde(rename = "joined")]
@@ -29,20 +49,27 @@ pub struct Protocol {
     pub left: Option<Left>,
 
     #[yaserde(rename = "joinPrepared")]
-    join_prepared: Option<JoinPrepared>,
+    pub join_prepared: Option<JoinPrepared>,
+
+    #[yaserde(rename = "joinRoom")]
+    pub join_room: Option<JoinRoom>,
 
     #[yaserde(rename = "errorpacket")]
-    error_packet: Option<ErrorPacket>,
+    pub error_packet: Option<ErrorPacket>,
 
     #[yaserde(rename = "close")]
-    close: Option<Close>,
+    pub close: Option<Close>,
 }
 
 #[derive(Clone, Debug, Default, PartialEq, YaDeserialize, YaSerialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 #[yaserde(rename = "join")]
 pub struct Join {}
 
 #[derive(Clone, Debug, Default, PartialEq, YaDeserialize, YaSerialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 #[yaserde(rename = "room")]
 pub struct Room {
     #[yaserde(attribute, rename = "roomId")]
@@ -52,19 +79,21 @@ pub struct Room {
     pub data: Data,
 }
 
-#[derive(Clone, Debug, Default, PartialEq, YaDeserialize, YaSerialize)]
-#[yaserde(rename = "data")]
+/// The payload of a room packet.
+///
+/// A move is written as the `<from>` and `<to>` children of `<data>` itself, which the derive
+/// can not express for an optional value, so the XML goes through the private `DataXml` helper.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub struct Data {
-    #[yaserde(attribute)]
     pub class: DataClass,
 
-    #[yaserde(attribute)]
     pub color: Option<String>,
 
-    #[yaserde(rename = "state")]
     pub state: Option<State>,
 
-    #[yaserde(flatten)]
+    #[cfg_attr(feature = "serde", serde(rename = "move"))]
     pub _move: Option<Move>,
 
     pub definition: Option<Definition>,
@@ -74,12 +103,81 @@ pub struct Data {
     pub winner: Option<Winner>,
 }
 
+#[derive(Default, YaDeserialize, YaSerialize)]
+#[yaserde(rename = "data")]
+struct DataXml {
+    #[yaserde(attribute)]
+    class: DataClass,
+
+    #[yaserde(attribute)]
+    color: Option<String>,
+
+    state: Option<State>,
+
+    from: Option<Coordinate>,
+
+    to: Option<Coordinate>,
+
+    definition: Option<Definition>,
+
+    scores: Option<Scores>,
+
+    winner: Option<Winner>,
+}
+
+impl YaDeserialize for Data {
+    fn deserialize<R: Read>(reader: &mut Deserializer<R>) -> Result<Self, String> {
+        let xml = DataXml::deserialize(reader)?;
+        let _move = match (xml.from, xml.to) {
+            (Some(from), Some(to)) => Some(Move { from, to }),
+            (None, None) => None,
+            (Some(_), None) => return Err("<data> has <from> without <to>".to_string()),
+            (None, Some(_)) => return Err("<data> has <to> without <from>".to_string()),
+        };
+        Ok(Data {
+            class: xml.class,
+            color: xml.color,
+            state: xml.state,
+            _move,
+            definition: xml.definition,
+            scores: xml.scores,
+            winner: xml.winner,
+        })
+    }
+}
+
+impl YaSerialize for Data {
+    fn serialize<W: Write>(&self, writer: &mut Serializer<W>) -> Result<(), String> {
+        let xml = DataXml {
+            class: self.class.clone(),
+            color: self.color.clone(),
+            state: self.state.clone(),
+            from: self._move.as_ref().map(|m| m.from.clone()),
+            to: self._move.as_ref().map(|m| m.to.clone()),
+            definition: self.definition.clone(),
+            scores: self.scores.clone(),
+            winner: self.winner.clone(),
+        };
+        xml.serialize(writer)
+    }
+
+    fn serialize_attributes(
+        &self,
+        attributes: Vec<OwnedAttribute>,
+        namespace: Namespace,
+    ) -> Result<(Vec<OwnedAttribute>, Namespace), String> {
+        Ok((attributes, namespace))
+    }
+}
+
 #[derive(Clone, Debug, Default, PartialEq, YaDeserialize, YaSerialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub enum DataClass {
     #[default]
     #[yaserde(rename = "welcomeMessage")]
     WelcomeMessage,
-    #[yaserde(rename = "momento")]
+    #[yaserde(rename = "memento")]
     Memento,
     #[yaserde(rename = "moveRequest")]
     MoveRequest,
@@ -92,7 +190,9 @@ pub enum DataClass {
 }
 
 
-#[derive(Clone, Debug, Default, PartialEq, YaDeserialize, YaSerialize)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, YaDeserialize, YaSerialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub struct Move {
     #[yaserde(rename = "from", child)]
     pub from: Coordinate,
@@ -100,7 +200,9 @@ pub struct Move {
     pub to: Coordinate,
 }
 
-#[derive(Clone, Debug, Default, PartialEq, YaDeserialize, YaSerialize)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, YaDeserialize, YaSerialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub struct Coordinate {
     #[yaserde(attribute)]
     pub q: i32,
@@ -113,6 +215,8 @@ pub struct Coordinate {
 }
 
 #[derive(Clone, Debug, Default, PartialEq, YaDeserialize, YaSerialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "SCREAMING_SNAKE_CASE"))]
 pub enum CubeDirection {
     #[default]
     #[yaserde(rename = "RIGHT")]
@@ -134,15 +238,20 @@ pub enum CubeDirection {
 }
 
 #[derive(Clone, Debug, Default, PartialEq, YaDeserialize, YaSerialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Water;
 
 #[derive(Clone, Debug, Default, PartialEq, YaDeserialize, YaSerialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Island;
 
 #[derive(Clone, Debug, Default, PartialEq, YaDeserialize, YaSerialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Sandbank;
 
 #[derive(Clone, Debug, Default, PartialEq, YaDeserialize, YaSerialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub struct Passenger {
     #[yaserde(attribute)]
     pub direction: CubeDirection,
@@ -151,25 +260,165 @@ pub struct Passenger {
 }
 
 #[derive(Clone, Debug, Default, PartialEq, YaDeserialize, YaSerialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Goal;
 
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "type", rename_all = "lowercase"))]
+pub enum Field {
+    Water,
+    Island,
+    Sandbank,
+    Passenger(Passenger),
+    Goal,
+}
 
-#[derive(Clone, Debug, Default, PartialEq, YaDeserialize, YaSerialize)]
-#[yaserde(rename = "field-array")]
+/// One column of a segment. The fields keep the order in which the server sends them,
+/// so `fields[i]` is the i-th field of the column.
+///
+/// Breaking change: this replaces the former `water`, `island`, `sandbank`, `passenger` and
+/// `goal` vectors, which lost the position of each field. Count fields of one type by
+/// filtering `fields`; the unit types [`Water`], [`Island`], [`Sandbank`] and [`Goal`] convert
+/// into a [`Field`].
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct FieldArray {
-    #[yaserde(rename = "water")]
-    pub water: Vec<Water>,
-    #[yaserde(rename = "island")]
-    pub island: Vec<Island>,
-    #[yaserde(rename = "sandbank")]
-    pub sandbank: Vec<Sandbank>,
-    #[yaserde(rename = "passenger")]
-    pub passenger: Vec<Passenger>,
-    #[yaserde(rename = "goal")]
-    pub goal: Vec<Goal>,
+    pub fields: Vec<Field>,
+}
+
+impl YaDeserialize for FieldArray {
+    fn deserialize<R: Read>(reader: &mut Deserializer<R>) -> Result<Self, String> {
+        let start_depth = reader.depth();
+        match reader.next_event()? {
+            XmlEvent::StartElement { .. } => {}
+            event => return Err(format!("Expected <field-array>, found {:?}", event)),
+        }
+
+        let mut fields = Vec::new();
+        loop {
+            match reader.peek()?.to_owned() {
+                XmlEvent::StartElement { name, attributes, .. } => {
+                    reader.next_event()?;
+                    fields.push(Field::from_xml(&name.local_name, &attributes)?);
+                }
+                XmlEvent::EndElement { .. } if reader.depth() == start_depth + 1 => break,
+                XmlEvent::EndDocument => return Err("Unexpected end of document in <field-array>".to_string()),
+                _ => {
+                    reader.next_event()?;
+                }
+            }
+        }
+
+        Ok(FieldArray { fields })
+    }
+}
+
+impl YaSerialize for FieldArray {
+    fn serialize<W: Write>(&self, writer: &mut Serializer<W>) -> Result<(), String> {
+        let skip = writer.skip_start_end();
+        if !skip {
+            let name = writer.get_start_event_name().unwrap_or_else(|| "field-array".to_string());
+            writer.write(WriterEvent::start_element(name.as_str())).map_err(|e| e.to_string())?;
+        }
+
+        for field in &self.fields {
+            match field {
+                Field::Passenger(passenger) => {
+                    writer.set_start_event_name(Some("passenger".to_string()));
+                    writer.set_skip_start_end(false);
+                    passenger.serialize(writer)?;
+                }
+                _ => {
+                    writer.write(WriterEvent::start_element(field.xml_name())).map_err(|e| e.to_string())?;
+                    writer.write(WriterEvent::end_element()).map_err(|e| e.to_string())?;
+                }
+            }
+        }
+
+        if !skip {
+            writer.write(WriterEvent::end_element()).map_err(|e| e.to_string())?;
+        }
+        Ok(())
+    }
+
+    fn serialize_attributes(
+        &self,
+        attributes: Vec<OwnedAttribute>,
+        namespace: Namespace,
+    ) -> Result<(Vec<OwnedAttribute>, Namespace), String> {
+        Ok((attributes, namespace))
+    }
+}
+
+impl From<Water> for Field {
+    fn from(_: Water) -> Self {
+        Field::Water
+    }
+}
+
+impl From<Island> for Field {
+    fn from(_: Island) -> Self {
+        Field::Island
+    }
+}
+
+impl From<Sandbank> for Field {
+    fn from(_: Sandbank) -> Self {
+        Field::Sandbank
+    }
+}
+
+impl From<Passenger> for Field {
+    fn from(passenger: Passenger) -> Self {
+        Field::Passenger(passenger)
+    }
+}
+
+impl From<Goal> for Field {
+    fn from(_: Goal) -> Self {
+        Field::Goal
+    }
+}
+
+impl Field {
+    fn xml_name(&self) -> &'static str {
+        match self {
+            Field::Water => "water",
+            Field::Island => "island",
+            Field::Sandbank => "sandbank",
+            Field::Passenger(_) => "passenger",
+            Field::Goal => "goal",
+        }
+    }
+
+    fn from_xml(name: &str, attributes: &[OwnedAttribute]) -> Result<Self, String> {
+        match name {
+            "water" => Ok(Field::Water),
+            "island" => Ok(Field::Island),
+            "sandbank" => Ok(Field::Sandbank),
+            "goal" => Ok(Field::Goal),
+            "passenger" => {
+                let mut passenger = Passenger::default();
+                for attribute in attributes {
+                    let value = attribute.value.as_str();
+                    match attribute.name.local_name.as_str() {
+                        "direction" => passenger.direction = value.parse()?,
+                        "passenger" => passenger.passenger = value.parse().map_err(|_| format!("Invalid passenger count: {}", value))?,
+                        _ => {}
+                    }
+                }
+                Ok(Field::Passenger(passenger))
+            }
+            _ => Err(format!("Unknown field type: {}", name)),
+        }
+    }
 }
 
 #[derive(Clone, Debug, Default, PartialEq, YaDeserialize, YaSerialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "UPPERCASE"))]
 pub enum Team {
     #[default]
     #[yaserde(rename = "ONE")]
@@ -179,6 +428,8 @@ pub enum Team {
 }
 
 #[derive(Clone, Debug, Default, PartialEq, YaDeserialize, YaSerialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub struct Ship {
     #[yaserde(attribute)]
     pub team: Team,
@@ -205,6 +456,8 @@ pub struct Ship {
 }
 
 #[derive(Clone, Debug, Default, PartialEq, YaDeserialize, YaSerialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 #[yaserde(rename = "state")]
 pub struct State {
     #[yaserde(attribute)]
@@ -225,6 +478,8 @@ pub struct State {
 }
 
 #[derive(Clone, Debug, Default, PartialEq, YaDeserialize, YaSerialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 pub struct Segment {
     #[yaserde(attribute)]
     pub direction: CubeDirection,
@@ -236,6 +491,8 @@ pub struct Segment {
 }
 
 #[derive(Clone, Debug, Default, PartialEq, YaDeserialize, YaSerialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 #[yaserde(rename = "board")]
 pub struct Board {
     #[yaserde(attribute, rename = "nextDirection")]
@@ -245,6 +502,8 @@ pub struct Board {
 }
 
 #[derive(Clone, Debug, Default, PartialEq, YaDeserialize, YaSerialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 #[yaserde(rename = "list")]
 pub struct List {
     #[yaserde(rename = "field")]
@@ -252,6 +511,8 @@ pub struct List {
 }
 
 #[derive(Clone, Debug, Default, PartialEq, YaDeserialize, YaSerialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 #[yaserde(rename = "fishes")]
 pub struct Fishes {
     #[yaserde(rename = "int")]
@@ -260,70 +521,89 @@ pub struct Fishes {
 
 
 #[derive(Clone, Default, PartialEq, Debug, YaDeserialize, YaSerialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 #[yaserde(rename = "definition")]
 pub struct Definition {
     #[yaserde(rename = "fragment")]
-    fragments: Vec<Fragment>,
+    pub(crate) fragments: Vec<Fragment>,
 }
 
 #[derive(Clone, Default, PartialEq, Debug, YaDeserialize, YaSerialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 #[yaserde(rename = "fragment")]
 pub struct Fragment {
     #[yaserde(attribute)]
-    name: String,
-    aggregation: String,
-    relevant_for_ranking: bool,
+    pub(crate) name: String,
+    pub(crate) aggregation: String,
+    #[yaserde(rename = "relevantForRanking")]
+    pub(crate) relevant_for_ranking: bool,
 }
 
 #[derive(Clone, Default, PartialEq, Debug, YaDeserialize, YaSerialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 #[yaserde(rename = "scores")]
 pub struct Scores {
     #[yaserde(rename = "entry")]
-    entries: Vec<Entry>,
+    pub(crate) entries: Vec<Entry>,
 }
 
 #[derive(Clone, Default, PartialEq, Debug, YaDeserialize, YaSerialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 #[yaserde(rename = "entry")]
 pub struct Entry {
-    player: Player,
-    score: Score,
+    pub(crate) player: Player,
+    pub(crate) score: Score,
 }
 
 #[derive(Clone, Default, PartialEq, Debug, YaDeserialize, YaSerialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 #[yaserde(rename = "player")]
 pub struct Player {
     #[yaserde(attribute)]
-    name: String,
+    pub(crate) name: String,
     #[yaserde(attribute)]
-    team: Team,
+    pub(crate) team: Team,
 }
 
 #[derive(Clone, Default, PartialEq, Debug, YaDeserialize, YaSerialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 #[yaserde(rename = "score")]
 pub struct Score {
     #[yaserde(attribute)]
-    cause: String,
+    pub(crate) cause: String,
     #[yaserde(attribute)]
-    reason: String,
+    pub(crate) reason: String,
     #[yaserde(rename = "part")]
-    parts: Vec<i32>,
+    pub(crate) parts: Vec<i32>,
 }
 
 #[derive(Clone, Default, PartialEq, Debug, YaDeserialize, YaSerialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 #[yaserde(rename = "winner")]
 pub struct Winner {
     #[yaserde(attribute)]
-    team: Team,
+    pub(crate) team: Team,
 }
 
-#[derive(Clone, Debug, Default, YaDeserialize, YaSerialize)]
+#[derive(Clone, Debug, Default, PartialEq, YaDeserialize, YaSerialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 #[yaserde(rename = "left")]
 pub struct Left {
-    #[yaserde(attribute)]
+    #[yaserde(attribute, rename = "roomId")]
     pub room_id: String,
 }
 
-#[derive(Clone, Debug, Default, YaDeserialize, YaSerialize)]
+#[derive(Clone, Debug, Default, PartialEq, YaDeserialize, YaSerialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 #[yaserde(rename = "joined")]
 pub struct Joined {
     #[yaserde(attribute, rename = "roomId")]
@@ -331,6 +611,8 @@ pub struct Joined {
 }
 
 #[derive(Clone, Default, PartialEq, Debug, YaDeserialize, YaSerialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 #[yaserde(rename = "joinPrepared")]
 pub struct JoinPrepared {
     #[yaserde(attribute, rename = "reservationCode")]
@@ -338,25 +620,114 @@ pub struct JoinPrepared {
 }
 
 #[derive(Clone, Default, PartialEq, Debug, YaDeserialize, YaSerialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+#[yaserde(rename = "joinRoom")]
+pub struct JoinRoom {
+    #[yaserde(attribute, rename = "roomId")]
+    pub room_id: String,
+}
+
+/// Sent by the server when it can not process a request, e.g. an unknown reservation code.
+#[derive(Clone, Default, PartialEq, Debug, YaDeserialize, YaSerialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 #[yaserde(rename = "errorpacket")]
 pub struct ErrorPacket {
     #[yaserde(attribute, rename = "message")]
     pub message: String,
 
+    /// The request that caused the error, if the server could decode it.
     #[yaserde(rename = "originalRequest")]
-    original_request: OriginalRequest,
+    pub original_request: Option<OriginalRequest>,
 }
 
-#[derive(Clone, Default, PartialEq, Debug, YaDeserialize, YaSerialize)]
+/// The request an [`ErrorPacket`] refers to, written by the server as
+/// `<originalRequest class="...">` with the attributes or children of that request.
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "class", rename_all = "camelCase"))]
+pub enum OriginalRequest {
+    Join,
+    #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+    JoinPrepared { reservation_code: String },
+    #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+    JoinRoom { room_id: String },
+    Move(Move),
+    /// A request class this client does not know.
+    #[cfg_attr(feature = "serde", serde(untagged))]
+    Other(String),
+}
+
+#[derive(Default, YaDeserialize, YaSerialize)]
 #[yaserde(rename = "originalRequest")]
-pub struct OriginalRequest {
-    #[yaserde(attribute, rename = "class")]
+struct OriginalRequestXml {
+    #[yaserde(attribute)]
     class: String,
 
     #[yaserde(attribute, rename = "reservationCode")]
-    reservation_code: String,
+    reservation_code: Option<String>,
+
+    #[yaserde(attribute, rename = "roomId")]
+    room_id: Option<String>,
+
+    from: Option<Coordinate>,
+
+    to: Option<Coordinate>,
+}
+
+impl YaDeserialize for OriginalRequest {
+    fn deserialize<R: Read>(reader: &mut Deserializer<R>) -> Result<Self, String> {
+        let xml = OriginalRequestXml::deserialize(reader)?;
+        Ok(match (xml.class.as_str(), xml.from, xml.to) {
+            ("join", _, _) => OriginalRequest::Join,
+            ("joinPrepared", _, _) => OriginalRequest::JoinPrepared { reservation_code: xml.reservation_code.unwrap_or_default() },
+            ("joinRoom", _, _) => OriginalRequest::JoinRoom { room_id: xml.room_id.unwrap_or_default() },
+            ("move", Some(from), Some(to)) => OriginalRequest::Move(Move { from, to }),
+            _ => OriginalRequest::Other(xml.class),
+        })
+    }
+}
+
+impl YaSerialize for OriginalRequest {
+    fn serialize<W: Write>(&self, writer: &mut Serializer<W>) -> Result<(), String> {
+        let mut xml = OriginalRequestXml { class: self.class().to_string(), ..Default::default() };
+        match self {
+            OriginalRequest::JoinPrepared { reservation_code } => xml.reservation_code = Some(reservation_code.clone()),
+            OriginalRequest::JoinRoom { room_id } => xml.room_id = Some(room_id.clone()),
+            OriginalRequest::Move(m) => {
+                xml.from = Some(m.from.clone());
+                xml.to = Some(m.to.clone());
+            }
+            OriginalRequest::Join | OriginalRequest::Other(_) => {}
+        }
+        xml.serialize(writer)
+    }
+
+    fn serialize_attributes(
+        &self,
+        attributes: Vec<OwnedAttribute>,
+        namespace: Namespace,
+    ) -> Result<(Vec<OwnedAttribute>, Namespace), String> {
+        Ok((attributes, namespace))
+    }
+}
+
+impl OriginalRequest {
+    /// The `class` attribute of the request.
+    pub fn class(&self) -> &str {
+        match self {
+            OriginalRequest::Join => "join",
+            OriginalRequest::JoinPrepared { .. } => "joinPrepared",
+            OriginalRequest::JoinRoom { .. } => "joinRoom",
+            OriginalRequest::Move(_) => "move",
+            OriginalRequest::Other(class) => class,
+        }
+    }
 }
 
 #[derive(Clone, Default, PartialEq, Debug, YaDeserialize, YaSerialize)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
 #[yaserde(rename = "close")]
 pub struct Close {}
\ No newline at end of file
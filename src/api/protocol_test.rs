@@ -1,6 +1,83 @@
 #[cfg(test)]
 mod tests {
-    use crate::api::protocol::{Move, Join, DataClass, Coordinate, Data, Room, CubeDirection, FieldArray, Water, Island, Sandbank, Passenger, Goal, State, Ship, Segment, Board, Team};
+    use yaserde::{YaDeserialize, YaSerialize};
+
+    use crate::api::protocol::{to_xml, Move, Join, DataClass, Coordinate, Data, Room, CubeDirection, Field, FieldArray, Passenger, State, Ship, Segment, Board, Team, Protocol, Joined, Left, JoinPrepared, JoinRoom, Close, ErrorPacket, OriginalRequest, Water, Island, Sandbank, Goal};
+    use crate::random::Random;
+
+    /// Serializes `value`, parses the XML again and checks that nothing was lost.
+    fn round_trip<T: YaSerialize + YaDeserialize + PartialEq + std::fmt::Debug>(value: &T) -> String {
+        let xml = to_xml(value).unwrap();
+        let parsed: T = yaserde::de::from_str(&xml).unwrap_or_else(|error| panic!("{}: {}", error, xml));
+        assert_eq!(&parsed, value, "{}", xml);
+        xml
+    }
+
+    fn parse<T: YaDeserialize>(xml: &str) -> T {
+        yaserde::de::from_str(xml).unwrap()
+    }
+
+    fn random_team(random: &mut Random) -> Team {
+        if random.below(2) == 0 { Team::One } else { Team::Two }
+    }
+
+    /// Text with characters that must be escaped in attributes and text nodes.
+    fn random_text(random: &mut Random) -> String {
+        const CHARS: &[char] = &['a', 'Z', '0', ' ', '-', '&', '<', '>', '"', '\'', 'ä'];
+        (0..random.below(12)).map(|_| CHARS[random.below(CHARS.len())]).collect()
+    }
+
+    fn random_coordinate(random: &mut Random) -> Coordinate {
+        Coordinate::new(random.below(21) as i32 - 10, random.below(21) as i32 - 10)
+    }
+
+    fn random_state(random: &mut Random) -> State {
+        let ship = |random: &mut Random, team| Ship {
+            team,
+            direction: CubeDirection::HEX[random.below(6)].clone(),
+            speed: random.below(6) as i32 + 1,
+            coal: random.below(7) as i32,
+            passengers: random.below(3) as i32,
+            free_turns: random.below(2) as i32,
+            points: random.below(60) as i32,
+            position: random_coordinate(random),
+        };
+        State {
+            class: "state".to_string(),
+            start_team: random_team(random),
+            turn: random.below(60) as i32,
+            current_team: random_team(random),
+            board: Board::generate(random.next_u64()),
+            ship: vec![ship(random, Team::One), ship(random, Team::Two)],
+        }
+    }
+
+    fn random_result(random: &mut Random) -> Data {
+        let aggregation = |random: &mut Random| if random.below(2) == 0 { "SUM" } else { "AVERAGE" };
+        let relevant = |random: &mut Random| random.below(2) == 0;
+        let causes = ["REGULAR", "LEFT", "RULE_VIOLATION", "SOFT_TIMEOUT", "HARD_TIMEOUT", "UNKNOWN"];
+        let mut entries = String::new();
+        for team in ["ONE", "TWO"] {
+            entries.push_str(&format!(
+                r#"<entry><player name="{}" team="{}"/><score cause="{}" reason="{}"><part>{}</part><part>{}</part></score></entry>"#,
+                escape(&random_text(random)), team, causes[random.below(causes.len())], escape(&random_text(random)),
+                random.below(3), random.below(60),
+            ));
+        }
+        let winner = match random.below(3) {
+            0 => String::new(),
+            n => format!(r#"<winner team="{}"/>"#, if n == 1 { "ONE" } else { "TWO" }),
+        };
+        let xml = format!(
+            r#"<data class="result"><definition><fragment name="Siegpunkte"><aggregation>{}</aggregation><relevantForRanking>{}</relevantForRanking></fragment><fragment name="Punkte"><aggregation>{}</aggregation><relevantForRanking>{}</relevantForRanking></fragment></definition><scores>{}</scores>{}</data>"#,
+            aggregation(random), relevant(random), aggregation(random), relevant(random), entries, winner,
+        );
+        parse(&xml)
+    }
+
+    fn escape(text: &str) -> String {
+        text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+    }
 
     #[test]
     fn test_join_deserialization() {
@@ -43,6 +120,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_memento_class() {
+        // The class name the server uses for game states.
+        let xml = r#"<room roomId="r"><data class="memento"><state class="state" startTeam="ONE" turn="3" currentTeam="TWO"><board nextDirection="RIGHT"/></state></data></room>"#;
+        let room: Room = yaserde::de::from_str(xml).unwrap();
+        assert_eq!(room.data.class, DataClass::Memento);
+        assert_eq!(room.data.state.as_ref().map(|state| state.turn), Some(3));
+        assert!(to_xml(&room).unwrap().contains(r#"class="memento""#));
+
+        // The old misspelling is no longer a memento; like any unknown class it falls back to the default.
+        let old: Room = yaserde::de::from_str(r#"<room roomId="r"><data class="momento"/></room>"#).unwrap();
+        assert_eq!(old.data.class, DataClass::WelcomeMessage);
+    }
+
     #[test]
     fn test_coordinate_deserialization() {
         let xml = r#"
@@ -71,7 +162,7 @@ mod tests {
 
         assert_eq!(
             field_array, 
-            FieldArray { water: vec![Water], island: vec![Island], sandbank: vec![Sandbank], passenger: vec![Passenger { direction: CubeDirection::Right, passenger: 1 }], goal: vec![Goal] }
+            FieldArray { fields: vec![Field::Water, Field::Island, Field::Sandbank, Field::Passenger(Passenger { direction: CubeDirection::Right, passenger: 1 }), Field::Goal] }
         );
     }
 
@@ -116,11 +207,7 @@ mod tests {
                         center: Coordinate { q: 0, r: 0, s: 0 }, 
                         field_array: vec![
                             FieldArray { 
-                                water: vec![Water, Water, Water, Water, Water], 
-                                island: vec![], 
-                                sandbank: vec![], 
-                                passenger: vec![], 
-                                goal: vec![] }
+                                fields: vec![Field::Water, Field::Water, Field::Water, Field::Water, Field::Water] }
                         ] }] }, 
                         ship: vec![
                             Ship { 
@@ -143,4 +230,125 @@ mod tests {
                                 position: Coordinate { q: -2, r: 1, s: 1 } }] }
         );
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_field_from_unit_types() {
+        let passenger = Passenger { direction: CubeDirection::Left, passenger: 1 };
+        let fields: Vec<Field> = vec![Water.into(), Island.into(), Sandbank.into(), passenger.clone().into(), Goal.into()];
+        assert_eq!(fields, vec![Field::Water, Field::Island, Field::Sandbank, Field::Passenger(passenger), Field::Goal]);
+    }
+
+    #[test]
+    fn test_field_array_serialization() {
+        let field_array = FieldArray {
+            fields: vec![Field::Island, Field::Water, Field::Passenger(Passenger { direction: CubeDirection::Left, passenger: 0 }), Field::Goal],
+        };
+        let config = yaserde::ser::Config { write_document_declaration: false, ..Default::default() };
+        let xml = yaserde::ser::to_string_with_config(&field_array, &config).unwrap();
+        assert_eq!(xml, r#"<field-array><island /><water /><passenger direction="LEFT" passenger="0" /><goal /></field-array>"#);
+
+        let parsed: FieldArray = yaserde::de::from_str(&xml).unwrap();
+        assert_eq!(parsed, field_array);
+    }
+
+    #[test]
+    fn test_to_xml_has_no_declaration() {
+        let room = Room { room_id: "r".to_string(), data: Data::new_move(Move { from: Coordinate::new(0, 0), to: Coordinate::new(1, 0) }) };
+        assert_eq!(
+            round_trip(&room),
+            r#"<room roomId="r"><data class="move"><from q="0" r="0" s="0" /><to q="1" r="0" s="-1" /></data></room>"#
+        );
+    }
+
+    #[test]
+    fn test_data_without_move() {
+        let xml = round_trip(&parse::<Room>(r#"<room roomId="r"><data class="moveRequest"/></room>"#));
+        assert_eq!(xml, r#"<room roomId="r"><data class="moveRequest" /></room>"#);
+
+        let welcome: Room = parse(r#"<room roomId="r"><data class="welcomeMessage" color="TWO"/></room>"#);
+        assert_eq!(welcome.data._move, None);
+        assert_eq!(welcome.data.color.as_deref(), Some("TWO"));
+        round_trip(&welcome);
+    }
+
+    #[test]
+    fn test_data_with_half_a_move() {
+        let from_only = r#"<room roomId="r"><data class="move"><from q="0" r="0" s="0"/></data></room>"#;
+        let error = yaserde::de::from_str::<Room>(from_only).unwrap_err();
+        assert!(error.contains("<from> without <to>"), "{}", error);
+
+        let to_only = r#"<room roomId="r"><data class="move"><to q="1" r="0" s="-1"/></data></room>"#;
+        let error = yaserde::de::from_str::<Room>(to_only).unwrap_err();
+        assert!(error.contains("<to> without <from>"), "{}", error);
+    }
+
+    #[test]
+    fn test_result_serialization() {
+        let xml = r#"<room roomId="r"><data class="result"><definition><fragment name="Siegpunkte"><aggregation>SUM</aggregation><relevantForRanking>true</relevantForRanking></fragment></definition><scores><entry><player name="a" team="ONE" /><score cause="REGULAR" reason=""><part>2</part></score></entry></scores><winner team="ONE" /></data></room>"#;
+        assert_eq!(round_trip(&parse::<Room>(xml)), xml);
+    }
+
+    #[test]
+    fn test_small_packets_serialization() {
+        assert_eq!(round_trip(&Joined { room_id: "r".to_string() }), r#"<joined roomId="r" />"#);
+        assert_eq!(round_trip(&Left { room_id: "r".to_string() }), r#"<left roomId="r" />"#);
+        assert_eq!(round_trip(&JoinPrepared { reservation_code: "c".to_string() }), r#"<joinPrepared reservationCode="c" />"#);
+        assert_eq!(round_trip(&JoinRoom { room_id: "r".to_string() }), r#"<joinRoom roomId="r" />"#);
+        assert_eq!(round_trip(&Join {}), "<join />");
+        assert_eq!(round_trip(&Close {}), "<close />");
+
+        let error = ErrorPacket { message: "bad".to_string(), original_request: Some(OriginalRequest::JoinPrepared { reservation_code: "c".to_string() }) };
+        assert_eq!(round_trip(&error), r#"<errorpacket message="bad"><originalRequest class="joinPrepared" reservationCode="c" /></errorpacket>"#);
+    }
+
+    #[test]
+    fn test_state_round_trip_property() {
+        let mut random = Random::new(45);
+        for _ in 0..50 {
+            let state = random_state(&mut random);
+            round_trip(&state);
+            let room = Room { room_id: random_text(&mut random), data: Data { class: DataClass::Memento, state: Some(state), ..Default::default() } };
+            round_trip(&room);
+        }
+    }
+
+    #[test]
+    fn test_result_round_trip_property() {
+        let mut random = Random::new(46);
+        for _ in 0..50 {
+            round_trip(&Room { room_id: random_text(&mut random), data: random_result(&mut random) });
+        }
+    }
+
+    #[test]
+    fn test_protocol_round_trip_property() {
+        let mut random = Random::new(47);
+        for _ in 0..50 {
+            let mut protocol = Protocol::default();
+            match random.below(7) {
+                0 => protocol.joined = Some(Joined { room_id: random_text(&mut random) }),
+                1 => protocol.left = Some(Left { room_id: random_text(&mut random) }),
+                2 => protocol.join_prepared = Some(JoinPrepared { reservation_code: random_text(&mut random) }),
+                3 => protocol.close = Some(Close {}),
+                4 => {
+                    let from = random_coordinate(&mut random);
+                    let to = random_coordinate(&mut random);
+                    protocol.room = Some(Room { room_id: random_text(&mut random), data: Data::new_move(Move { from, to }) });
+                }
+                5 => protocol.join_room = Some(JoinRoom { room_id: random_text(&mut random) }),
+                _ => {
+                    let original_request = match random.below(6) {
+                        0 => None,
+                        1 => Some(OriginalRequest::Join),
+                        2 => Some(OriginalRequest::JoinPrepared { reservation_code: random_text(&mut random) }),
+                        3 => Some(OriginalRequest::JoinRoom { room_id: random_text(&mut random) }),
+                        4 => Some(OriginalRequest::Move(Move { from: random_coordinate(&mut random), to: random_coordinate(&mut random) })),
+                        _ => Some(OriginalRequest::Other("observe".to_string())),
+                    };
+                    protocol.error_packet = Some(ErrorPacket { message: random_text(&mut random), original_request });
+                }
+            }
+            round_trip(&protocol);
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use flate2::write::GzEncoder;
+use flate2::Compression;
+
+/// Writes the packets of a game into a replay file in the format of the server.
+///
+/// Every packet is preceded by a comment with its direction and the time in milliseconds since
+/// the Unix epoch, e.g. `<!-- received 1718000000000 -->`. The `<protocol>` root is opened on
+/// creation and closed by [`Recorder::finish`] or when the recorder is dropped, so the file can
+/// be loaded with [`Replay`](crate::api::replay::Replay).
+pub struct Recorder {
+    writer: Output,
+    finished: bool,
+}
+
+/// Gzip output is kept apart, as it is only complete once the encoder wrote its trailer.
+enum Output {
+    Plain(Box<dyn Write + Send>),
+    Gzip(GzEncoder<BufWriter<File>>),
+}
+
+impl Output {
+    fn finish(&mut self) -> io::Result<()> {
+        match self {
+            Output::Plain(writer) => writer.flush(),
+            Output::Gzip(encoder) => {
+                encoder.try_finish()?;
+                encoder.get_mut().flush()
+            }
+        }
+    }
+}
+
+impl Write for Output {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match self {
+            Output::Plain(writer) => writer.write(buf),
+            Output::Gzip(encoder) => encoder.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self {
+            Output::Plain(writer) => writer.flush(),
+            Output::Gzip(encoder) => encoder.flush(),
+        }
+    }
+}
+
+impl Recorder {
+    pub fn new(writer: impl Write + Send + 'static) -> io::Result<Self> {
+        Recorder::with_output(Output::Plain(Box::new(writer)))
+    }
+
+    /// Creates the replay file at `path`, gzip compressed if the name ends with `.gz`.
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = BufWriter::new(File::create(path.as_ref())?);
+        if path.as_ref().extension().is_some_and(|extension| extension == "gz") {
+            Recorder::with_output(Output::Gzip(GzEncoder::new(file, Compression::default())))
+        } else {
+            Recorder::new(file)
+        }
+    }
+
+    fn with_output(writer: Output) -> io::Result<Self> {
+        let mut recorder = Recorder { writer, finished: false };
+        recorder.writer.write_all(b"<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<protocol>\n")?;
+        Ok(recorder)
+    }
+
+    pub fn received(&mut self, packet: &str) -> io::Result<()> {
+        self.record("received", packet)
+    }
+
+    pub fn sent(&mut self, packet: &str) -> io::Result<()> {
+        self.record("sent", packet)
+    }
+
+    /// Closes the `<protocol>` root and flushes the file. A gzip file is complete afterwards, even
+    /// while the recorder is still alive.
+    pub fn finish(&mut self) -> io::Result<()> {
+        if !self.finished {
+            self.finished = true;
+            self.writer.write_all(b"</protocol>\n")?;
+        }
+        self.writer.finish()
+    }
+
+    fn record(&mut self, direction: &str, packet: &str) -> io::Result<()> {
+        // The opening tag of the connection is already the root of the file.
+        let packet = packet.trim().trim_start_matches("<protocol>").trim();
+        if packet.is_empty() || self.finished {
+            return Ok(());
+        }
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis();
+        writeln!(self.writer, "  <!-- {} {} -->\n  {}", direction, timestamp, packet)
+    }
+}
+
+impl Drop for Recorder {
+    fn drop(&mut self) {
+        let _ = self.finish();
+    }
+}
@@ -0,0 +1,93 @@
+#[cfg(test)]
+mod tests {
+    use std::io::{self, Write};
+    use std::sync::{Arc, Mutex};
+
+    use crate::api::recorder::Recorder;
+    use crate::api::replay::Replay;
+
+    /// Writer that keeps the bytes accessible after the recorder took ownership.
+    #[derive(Clone, Default)]
+    struct Shared(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Shared {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    const MEMENTO: &str = r#"<room roomId="r"><data class="memento"><state class="state" startTeam="ONE" turn="3" currentTeam="TWO"><board nextDirection="RIGHT"></board></state></data></room>"#;
+
+    fn record(packets: &[(bool, &str)]) -> String {
+        let output = Shared::default();
+        let mut recorder = Recorder::new(output.clone()).unwrap();
+        for (received, packet) in packets {
+            if *received {
+                recorder.received(packet).unwrap();
+            } else {
+                recorder.sent(packet).unwrap();
+            }
+        }
+        drop(recorder);
+        let bytes = output.0.lock().unwrap().clone();
+        String::from_utf8(bytes).unwrap()
+    }
+
+    #[test]
+    fn test_format() {
+        let xml = record(&[(false, "<protocol><join/>"), (true, r#"<joined roomId="r"/>"#)]);
+        let lines: Vec<&str> = xml.lines().collect();
+        assert_eq!(lines[0], r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+        assert_eq!(lines[1], "<protocol>");
+        assert!(lines[2].starts_with("  <!-- sent ") && lines[2].ends_with(" -->"));
+        assert_eq!(lines[3], "  <join/>");
+        assert!(lines[4].starts_with("  <!-- received "));
+        assert_eq!(lines[5], r#"  <joined roomId="r"/>"#);
+        assert_eq!(lines[6], "</protocol>");
+        assert_eq!(lines.len(), 7);
+
+        let timestamp: u128 = lines[2].trim_start_matches("  <!-- sent ").trim_end_matches(" -->").parse().unwrap();
+        assert!(timestamp > 1_600_000_000_000);
+    }
+
+    #[test]
+    fn test_recording_is_a_replay() {
+        let sent_move = r#"<room roomId="r"><data class="move"><from q="0" r="0" s="0"/><to q="1" r="0" s="-1"/></data></room>"#;
+        let xml = record(&[(false, "<protocol><join/>"), (true, MEMENTO), (false, sent_move), (true, MEMENTO)]);
+        let replay = Replay::parse(&xml).unwrap();
+        assert_eq!(replay.room_id.as_deref(), Some("r"));
+        assert_eq!(replay.states.len(), 2);
+        assert_eq!(replay.states[0].turn, 3);
+    }
+
+    #[test]
+    fn test_gzip_file() {
+        let path = std::env::temp_dir().join(format!("socha-recorder-{}.xml.gz", std::process::id()));
+        let mut recorder = Recorder::create(&path).unwrap();
+        recorder.received(MEMENTO).unwrap();
+        recorder.finish().unwrap();
+        drop(recorder);
+
+        let bytes = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(bytes[..2], [0x1f, 0x8b]);
+        assert_eq!(Replay::read(bytes.as_slice()).unwrap().states.len(), 1);
+    }
+
+    #[test]
+    fn test_gzip_file_complete_after_finish() {
+        let path = std::env::temp_dir().join(format!("socha-recorder-finish-{}.xml.gz", std::process::id()));
+        let mut recorder = Recorder::create(&path).unwrap();
+        recorder.received(MEMENTO).unwrap();
+        recorder.finish().unwrap();
+
+        let replay = Replay::load(&path);
+        drop(recorder);
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(replay.unwrap().states.len(), 1);
+    }
+}
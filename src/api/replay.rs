@@ -0,0 +1,150 @@
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Read};
+use std::path::Path;
+
+use flate2::read::GzDecoder;
+use regex::Regex;
+use yaserde::de::from_str;
+
+use crate::api::protocol::{Data, DataClass, Move, Room, State};
+use crate::api::result::GameResult;
+
+/// First two bytes of every gzip stream.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// A game as stored in a replay file of the server.
+///
+/// Replays are XML documents with a `<protocol>` root holding one `<room>` element per
+/// packet, usually a memento for every turn and the result at the end. Other packets and
+/// comments between the rooms are ignored.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Replay {
+    pub room_id: Option<String>,
+    /// States of all mementos in file order.
+    pub states: Vec<State>,
+    /// Data of the result packet, if the game was finished.
+    pub result: Option<Data>,
+}
+
+#[derive(Debug)]
+pub enum ReplayError {
+    Io(io::Error),
+    InvalidXml(String),
+    ParseError(String),
+}
+
+impl Error for ReplayError {}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReplayError::Io(error) => write!(f, "Could not read replay: {}", error),
+            ReplayError::InvalidXml(msg) => write!(f, "Replay is not valid XML: {}", msg),
+            ReplayError::ParseError(msg) => write!(f, "Could not parse replay packet: {}", msg),
+        }
+    }
+}
+
+/// Why [`infer_move`] found no single move between two states.
+#[derive(Clone, Debug, PartialEq)]
+pub enum InferenceError {
+    /// No legal move of the first state leads to the second one.
+    NoMove,
+    /// Several legal moves lead to the second state.
+    Ambiguous(Vec<Move>),
+}
+
+impl Error for InferenceError {}
+
+impl fmt::Display for InferenceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InferenceError::NoMove => write!(f, "No legal move leads from the first state to the second"),
+            InferenceError::Ambiguous(moves) => write!(f, "{} moves lead to the same state", moves.len()),
+        }
+    }
+}
+
+impl From<io::Error> for ReplayError {
+    fn from(error: io::Error) -> Self {
+        ReplayError::Io(error)
+    }
+}
+
+impl Replay {
+    /// Loads a replay file, which may be gzip compressed.
+    pub fn load(path: impl AsRef<Path>) -> Result<Replay, ReplayError> {
+        Replay::read(File::open(path)?)
+    }
+
+    /// Reads a replay from plain or gzip compressed bytes.
+    pub fn read(mut reader: impl Read) -> Result<Replay, ReplayError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+        if bytes.starts_with(&GZIP_MAGIC) {
+            let mut decompressed = Vec::new();
+            GzDecoder::new(bytes.as_slice()).read_to_end(&mut decompressed)?;
+            bytes = decompressed;
+        }
+        let xml = String::from_utf8(bytes).map_err(|error| ReplayError::InvalidXml(error.to_string()))?;
+        Replay::parse(&xml)
+    }
+
+    pub fn parse(xml: &str) -> Result<Replay, ReplayError> {
+        if !xml.contains("<protocol") {
+            return Err(ReplayError::InvalidXml("missing <protocol> root".to_string()));
+        }
+
+        let rooms = Regex::new(r"<room[\s>][\s\S]*?</room>").unwrap();
+        let mut replay = Replay::default();
+        for found in rooms.find_iter(xml) {
+            let room: Room = from_str(found.as_str()).map_err(ReplayError::ParseError)?;
+            replay.room_id.get_or_insert_with(|| room.room_id.clone());
+            match room.data.class {
+                DataClass::Memento => replay.states.extend(room.data.state),
+                DataClass::Result => replay.result = Some(room.data),
+                _ => {}
+            }
+        }
+        Ok(replay)
+    }
+
+    pub fn final_state(&self) -> Option<&State> {
+        self.states.last()
+    }
+
+    pub fn game_result(&self) -> Option<GameResult> {
+        self.result.as_ref().and_then(GameResult::from_data)
+    }
+
+    /// The move between every two consecutive states, see [`infer_move`].
+    pub fn moves(&self) -> Vec<Result<Move, InferenceError>> {
+        self.states.windows(2).map(|pair| infer_move(&pair[0], &pair[1])).collect()
+    }
+}
+
+/// Finds the move that leads from `before` to `after` by trying every legal move of `before`
+/// with the rules engine. States match if the ships, the turn and the team to move are equal;
+/// the rest of the board follows from the ships or is revealed by the server.
+pub fn infer_move(before: &State, after: &State) -> Result<Move, InferenceError> {
+    let mut state = before.clone();
+    let mut matches: Vec<Move> = state
+        .legal_moves()
+        .into_iter()
+        .filter(|m| {
+            let Ok(undo) = state.make_move(m) else {
+                return false;
+            };
+            let found = state.ship == after.ship && state.turn == after.turn && state.current_team == after.current_team;
+            state.unmake_move(undo);
+            found
+        })
+        .collect();
+    match matches.len() {
+        0 => Err(InferenceError::NoMove),
+        1 => Ok(matches.remove(0)),
+        _ => Err(InferenceError::Ambiguous(matches)),
+    }
+}
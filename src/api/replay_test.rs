@@ -0,0 +1,151 @@
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+
+    use crate::api::protocol::{Board, DataClass, State, Team};
+    use crate::api::replay::{infer_move, InferenceError, Replay, ReplayError};
+    use crate::random::Random;
+
+    fn memento(turn: i32, team: &str) -> String {
+        format!(
+            r#"
+  <room roomId="f1b0d3c2">
+    <data class="memento">
+      <state class="state" startTeam="ONE" turn="{}" currentTeam="{}">
+        <board nextDirection="RIGHT">
+          <segment direction="RIGHT">
+            <center q="0" r="0" s="0"/>
+            <field-array>
+              <water/>
+              <water/>
+              <island/>
+              <water/>
+              <water/>
+            </field-array>
+          </segment>
+        </board>
+        <ship team="ONE" direction="RIGHT" speed="1" coal="6" passengers="0" freeTurns="1" points="0">
+          <position q="-1" r="-1" s="2"/>
+        </ship>
+        <ship team="TWO" direction="RIGHT" speed="1" coal="6" passengers="0" freeTurns="1" points="0">
+          <position q="-2" r="1" s="1"/>
+        </ship>
+      </state>
+    </data>
+  </room>"#,
+            turn, team
+        )
+    }
+
+    fn replay_xml() -> String {
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<protocol>{}{}
+  <room roomId="f1b0d3c2">
+    <data class="result">
+      <definition>
+        <fragment name="Siegpunkte">
+          <aggregation>SUM</aggregation>
+          <relevantForRanking>true</relevantForRanking>
+        </fragment>
+      </definition>
+      <scores>
+        <entry>
+          <player name="one" team="ONE"/>
+          <score cause="REGULAR" reason="">
+            <part>2</part>
+          </score>
+        </entry>
+        <entry>
+          <player name="two" team="TWO"/>
+          <score cause="REGULAR" reason="">
+            <part>0</part>
+          </score>
+        </entry>
+      </scores>
+      <winner team="ONE"/>
+    </data>
+  </room>
+</protocol>
+"#,
+            memento(0, "ONE"),
+            memento(1, "TWO")
+        )
+    }
+
+    #[test]
+    fn test_parse() {
+        let replay = Replay::parse(&replay_xml()).unwrap();
+        assert_eq!(replay.room_id.as_deref(), Some("f1b0d3c2"));
+        assert_eq!(replay.states.len(), 2);
+        assert_eq!(replay.states[0].turn, 0);
+        assert_eq!(replay.final_state().unwrap().current_team, Team::Two);
+        assert_eq!(replay.states[1].board.segment[0].field_array[0].fields.len(), 5);
+
+        let game_result = replay.game_result().unwrap();
+        assert_eq!(game_result.winner, Some(Team::One));
+        assert_eq!(game_result.player(&Team::Two).unwrap().part("Siegpunkte"), Some(0));
+
+        let result = replay.result.unwrap();
+        assert_eq!(result.class, DataClass::Result);
+        assert!(result.scores.is_some());
+        assert!(result.winner.is_some());
+    }
+
+    #[test]
+    fn test_read_gzip() {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(replay_xml().as_bytes()).unwrap();
+        let compressed = encoder.finish().unwrap();
+
+        let replay = Replay::read(compressed.as_slice()).unwrap();
+        assert_eq!(replay, Replay::read(replay_xml().as_bytes()).unwrap());
+        assert_eq!(replay.states.len(), 2);
+    }
+
+    #[test]
+    fn test_unfinished_game() {
+        let replay = Replay::parse(&format!("<protocol>{}", memento(0, "ONE"))).unwrap();
+        assert_eq!(replay.states.len(), 1);
+        assert_eq!(replay.result, None);
+    }
+
+    #[test]
+    fn test_errors() {
+        assert!(matches!(Replay::parse("<room/>"), Err(ReplayError::InvalidXml(_))));
+        assert!(matches!(Replay::read([0xff, 0xfe].as_slice()), Err(ReplayError::InvalidXml(_))));
+        assert!(matches!(Replay::load("does/not/exist.xml"), Err(ReplayError::Io(_))));
+        assert!(matches!(Replay::read([0x1f, 0x8b, 0x00].as_slice()), Err(ReplayError::Io(_))));
+    }
+    #[test]
+    fn test_infer_moves() {
+        let mut state = State::start(Board::generate(11));
+        let mut random = Random::new(11);
+        let mut replay = Replay { states: vec![state.clone()], ..Default::default() };
+        let mut played = Vec::new();
+        for _ in 0..8 {
+            let moves = state.legal_moves();
+            let m = moves[random.below(moves.len())].clone();
+            state.make_move(&m).unwrap();
+            replay.states.push(state.clone());
+            played.push(Ok(m));
+        }
+        assert_eq!(replay.moves(), played);
+    }
+
+    #[test]
+    fn test_infer_move_errors() {
+        let start = State::start(Board::generate(11));
+        assert_eq!(infer_move(&start, &start), Err(InferenceError::NoMove));
+
+        let mut later = start.clone();
+        for _ in 0..2 {
+            let m = later.legal_moves()[0].clone();
+            later.make_move(&m).unwrap();
+        }
+        assert_eq!(infer_move(&start, &later), Err(InferenceError::NoMove));
+    }
+}
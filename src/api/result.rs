@@ -0,0 +1,226 @@
+use std::fmt;
+
+use crate::api::protocol::{Data, DataClass, Definition, Entry, Fragment, Player, Score, Scores, Team, Winner};
+
+/// Why a player got their score, as sent in the `cause` attribute of `<score>`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "SCREAMING_SNAKE_CASE"))]
+pub enum ScoreCause {
+    /// The game ended normally.
+    Regular,
+    /// The player left the game early.
+    Left,
+    /// The player sent an invalid move.
+    RuleViolation,
+    /// The player answered too late, but within the hard limit.
+    SoftTimeout,
+    /// The player did not answer at all.
+    HardTimeout,
+    #[default]
+    Unknown,
+}
+
+impl ScoreCause {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ScoreCause::Regular => "REGULAR",
+            ScoreCause::Left => "LEFT",
+            ScoreCause::RuleViolation => "RULE_VIOLATION",
+            ScoreCause::SoftTimeout => "SOFT_TIMEOUT",
+            ScoreCause::HardTimeout => "HARD_TIMEOUT",
+            ScoreCause::Unknown => "UNKNOWN",
+        }
+    }
+}
+
+impl From<&str> for ScoreCause {
+    /// Unrecognized causes map to `Unknown`, so newer servers do not break the client.
+    fn from(cause: &str) -> Self {
+        match cause {
+            "REGULAR" => ScoreCause::Regular,
+            "LEFT" => ScoreCause::Left,
+            "RULE_VIOLATION" => ScoreCause::RuleViolation,
+            "SOFT_TIMEOUT" => ScoreCause::SoftTimeout,
+            "HARD_TIMEOUT" => ScoreCause::HardTimeout,
+            _ => ScoreCause::Unknown,
+        }
+    }
+}
+
+impl fmt::Display for ScoreCause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Definition {
+    pub fn new(fragments: Vec<Fragment>) -> Self {
+        Definition { fragments }
+    }
+
+    pub fn fragments(&self) -> &[Fragment] {
+        &self.fragments
+    }
+}
+
+impl Fragment {
+    pub fn new(name: String, aggregation: String, relevant_for_ranking: bool) -> Self {
+        Fragment { name, aggregation, relevant_for_ranking }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// How the fragment is combined over several games, `SUM` or `AVERAGE`.
+    pub fn aggregation(&self) -> &str {
+        &self.aggregation
+    }
+
+    pub fn relevant_for_ranking(&self) -> bool {
+        self.relevant_for_ranking
+    }
+}
+
+impl Scores {
+    pub fn new(entries: Vec<Entry>) -> Self {
+        Scores { entries }
+    }
+
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+}
+
+impl Entry {
+    pub fn new(player: Player, score: Score) -> Self {
+        Entry { player, score }
+    }
+
+    pub fn player(&self) -> &Player {
+        &self.player
+    }
+
+    pub fn score(&self) -> &Score {
+        &self.score
+    }
+}
+
+impl Player {
+    pub fn new(name: String, team: Team) -> Self {
+        Player { name, team }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn team(&self) -> &Team {
+        &self.team
+    }
+}
+
+impl Score {
+    pub fn new(cause: ScoreCause, reason: String, parts: Vec<i32>) -> Self {
+        Score { cause: cause.as_str().to_string(), reason, parts }
+    }
+
+    pub fn cause(&self) -> ScoreCause {
+        ScoreCause::from(self.cause.as_str())
+    }
+
+    pub fn reason(&self) -> &str {
+        &self.reason
+    }
+
+    /// Values in the order of the fragments of the [`Definition`].
+    pub fn parts(&self) -> &[i32] {
+        &self.parts
+    }
+}
+
+impl Winner {
+    pub fn new(team: Team) -> Self {
+        Winner { team }
+    }
+
+    pub fn team(&self) -> &Team {
+        &self.team
+    }
+}
+
+impl Data {
+    /// A result packet, e.g. for a server stand-in. `winner` is `None` for a draw.
+    pub fn new_result(definition: Definition, scores: Scores, winner: Option<Team>) -> Self {
+        Data {
+            class: DataClass::Result,
+            definition: Some(definition),
+            scores: Some(scores),
+            winner: winner.map(Winner::new),
+            ..Default::default()
+        }
+    }
+}
+
+/// The result of a game with every score part labelled by its fragment name.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GameResult {
+    /// `None` for a draw.
+    pub winner: Option<Team>,
+    pub players: Vec<PlayerResult>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PlayerResult {
+    pub name: String,
+    pub team: Team,
+    pub cause: ScoreCause,
+    pub reason: String,
+    /// Fragment name and value, in the order of the definition.
+    pub parts: Vec<(String, i32)>,
+}
+
+impl GameResult {
+    /// Reads the result packet. Returns `None` for other packets or if definition or scores are
+    /// missing.
+    pub fn from_data(data: &Data) -> Option<GameResult> {
+        if data.class != DataClass::Result {
+            return None;
+        }
+        let fragments = data.definition.as_ref()?.fragments();
+        let players = data
+            .scores
+            .as_ref()?
+            .entries()
+            .iter()
+            .map(|entry| PlayerResult {
+                name: entry.player().name().to_string(),
+                team: entry.player().team().clone(),
+                cause: entry.score().cause(),
+                reason: entry.score().reason().to_string(),
+                parts: fragments
+                    .iter()
+                    .map(|fragment| fragment.name().to_string())
+                    .zip(entry.score().parts().iter().copied())
+                    .collect(),
+            })
+            .collect();
+        Some(GameResult { winner: data.winner.as_ref().map(|winner| winner.team().clone()), players })
+    }
+
+    pub fn player(&self, team: &Team) -> Option<&PlayerResult> {
+        self.players.iter().find(|player| &player.team == team)
+    }
+
+    /// Whether every player finished the game without a violation or timeout.
+    pub fn is_regular(&self) -> bool {
+        self.players.iter().all(|player| player.cause == ScoreCause::Regular)
+    }
+}
+
+impl PlayerResult {
+    pub fn part(&self, fragment: &str) -> Option<i32> {
+        self.parts.iter().find(|(name, _)| name == fragment).map(|&(_, value)| value)
+    }
+}
@@ -0,0 +1,103 @@
+#[cfg(test)]
+mod tests {
+    use crate::api::protocol::{Data, DataClass, Definition, Entry, Fragment, Player, Room, Score, Scores, Team};
+    use crate::api::result::{GameResult, ScoreCause};
+
+    const RESULT: &str = r#"
+        <room roomId="r">
+            <data class="result">
+                <definition>
+                    <fragment name="Siegpunkte">
+                        <aggregation>SUM</aggregation>
+                        <relevantForRanking>true</relevantForRanking>
+                    </fragment>
+                    <fragment name="Punkte">
+                        <aggregation>AVERAGE</aggregation>
+                        <relevantForRanking>false</relevantForRanking>
+                    </fragment>
+                </definition>
+                <scores>
+                    <entry>
+                        <player name="alice" team="ONE"/>
+                        <score cause="SOFT_TIMEOUT" reason="Zu langsam">
+                            <part>0</part>
+                            <part>14</part>
+                        </score>
+                    </entry>
+                    <entry>
+                        <player name="bob" team="TWO"/>
+                        <score cause="REGULAR" reason="">
+                            <part>2</part>
+                            <part>21</part>
+                        </score>
+                    </entry>
+                </scores>
+                <winner team="TWO"/>
+            </data>
+        </room>
+    "#;
+
+    fn result_data() -> Data {
+        yaserde::de::from_str::<Room>(RESULT).unwrap().data
+    }
+
+    #[test]
+    fn test_accessors() {
+        let data = result_data();
+        let fragments = data.definition.as_ref().unwrap().fragments();
+        assert_eq!(fragments.len(), 2);
+        assert_eq!(fragments[1].name(), "Punkte");
+        assert_eq!(fragments[1].aggregation(), "AVERAGE");
+        assert!(fragments[0].relevant_for_ranking());
+        assert!(!fragments[1].relevant_for_ranking());
+
+        let entry = &data.scores.as_ref().unwrap().entries()[0];
+        assert_eq!(entry.player().name(), "alice");
+        assert_eq!(entry.player().team(), &Team::One);
+        assert_eq!(entry.score().cause(), ScoreCause::SoftTimeout);
+        assert_eq!(entry.score().reason(), "Zu langsam");
+        assert_eq!(entry.score().parts(), &[0, 14]);
+        assert_eq!(data.winner.as_ref().unwrap().team(), &Team::Two);
+    }
+
+    #[test]
+    fn test_constructors() {
+        let definition = Definition::new(vec![
+            Fragment::new("Siegpunkte".to_string(), "SUM".to_string(), true),
+            Fragment::new("Punkte".to_string(), "AVERAGE".to_string(), false),
+        ]);
+        let scores = Scores::new(vec![
+            Entry::new(Player::new("alice".to_string(), Team::One), Score::new(ScoreCause::SoftTimeout, "Zu langsam".to_string(), vec![0, 14])),
+            Entry::new(Player::new("bob".to_string(), Team::Two), Score::new(ScoreCause::Regular, String::new(), vec![2, 21])),
+        ]);
+        assert_eq!(Data::new_result(definition, scores, Some(Team::Two)), result_data());
+    }
+
+    #[test]
+    fn test_score_cause() {
+        for cause in [ScoreCause::Regular, ScoreCause::Left, ScoreCause::RuleViolation, ScoreCause::SoftTimeout, ScoreCause::HardTimeout, ScoreCause::Unknown] {
+            assert_eq!(ScoreCause::from(cause.as_str()), cause);
+        }
+        assert_eq!(ScoreCause::from("SOMETHING_NEW"), ScoreCause::Unknown);
+        assert_eq!(ScoreCause::RuleViolation.to_string(), "RULE_VIOLATION");
+    }
+
+    #[test]
+    fn test_game_result() {
+        let result = GameResult::from_data(&result_data()).unwrap();
+        assert_eq!(result.winner, Some(Team::Two));
+        assert!(!result.is_regular());
+
+        let loser = result.player(&Team::One).unwrap();
+        assert_eq!(loser.cause, ScoreCause::SoftTimeout);
+        assert_eq!(loser.parts, vec![("Siegpunkte".to_string(), 0), ("Punkte".to_string(), 14)]);
+        assert_eq!(result.player(&Team::Two).unwrap().part("Punkte"), Some(21));
+        assert_eq!(result.player(&Team::Two).unwrap().part("Zeit"), None);
+    }
+
+    #[test]
+    fn test_game_result_of_other_packets() {
+        assert_eq!(GameResult::from_data(&Data { class: DataClass::Memento, ..Default::default() }), None);
+        assert_eq!(GameResult::from_data(&Data { class: DataClass::Result, ..Default::default() }), None);
+    }
+}
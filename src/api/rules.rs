@@ -0,0 +1,298 @@
+//! Simplified Mississippi Queen rules on top of the protocol [`State`].
+//!
+//! A [`Move`] goes in a straight line from the ship's position to `to`, which also turns
+//! the ship into the direction of travel:
+//!
+//! - Every field on the way must be revealed water, sandbank or goal. Islands, docks and the
+//!   other ship block the way. A sandbank may only be the last field and stops the ship at speed 1.
+//! - The new speed is the number of fields plus one for each field with current, at most
+//!   [`MAX_SPEED`]. Changing the speed by one is free, every further step costs one coal.
+//! - Turns beyond the ship's free turns cost one coal each. The free turns reset to one.
+//! - A ship at speed 1, or 2 on the current, picks up a passenger from an adjacent dock that faces it.
+//! - The points are five per segment, one per column and five per passenger.
+//!
+//! The teams alternate within a round, and at the start of a round the team with more points
+//! moves first, on a tie the start team.
+
+use std::error::Error;
+use std::fmt;
+
+use crate::api::protocol::{Board, Coordinate, CubeDirection, Field, Move, Ship, State, Team};
+
+pub const MAX_SPEED: i32 = 6;
+
+/// The game ends after this many turns, counted over both teams.
+pub const MAX_TURNS: i32 = 60;
+
+/// Passengers a ship needs on board to finish on a goal field.
+pub const PASSENGERS_TO_FINISH: i32 = 2;
+
+pub const POINTS_PER_SEGMENT: i32 = 5;
+pub const POINTS_PER_PASSENGER: i32 = 5;
+
+/// Why [`State::make_move`] rejected a move.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RulesError {
+    /// The last of the [`MAX_TURNS`] turns has been played.
+    GameOver,
+    /// `from` is not the position of the current team's ship.
+    NotYourShip(Coordinate),
+    /// `to` is not reachable on a straight line from `from`.
+    NotStraight(Coordinate),
+    /// The field is unrevealed, an island, a dock, the other ship, or a sandbank that is not the last field.
+    Blocked(Coordinate),
+    TooFast(i32),
+    NotEnoughCoal(i32),
+}
+
+impl Error for RulesError {}
+
+impl fmt::Display for RulesError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RulesError::GameOver => write!(f, "The game is over"),
+            RulesError::NotYourShip(coordinate) => write!(f, "No ship of the current team at {}", coordinate),
+            RulesError::NotStraight(coordinate) => write!(f, "{} is not on a straight line", coordinate),
+            RulesError::Blocked(coordinate) => write!(f, "The way is blocked at {}", coordinate),
+            RulesError::TooFast(speed) => write!(f, "Speed {} is above {}", speed, MAX_SPEED),
+            RulesError::NotEnoughCoal(cost) => write!(f, "The move needs {} coal", cost),
+        }
+    }
+}
+
+/// Everything [`State::unmake_move`] needs to restore the state before a move.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Undo {
+    pub(crate) ship: usize,
+    pub(crate) before: Ship,
+    /// Segment, column and row of the dock a passenger was picked up from.
+    pub(crate) dock: Option<(usize, usize, usize)>,
+    pub(crate) turn: i32,
+    pub(crate) current_team: Team,
+}
+
+/// The outcome of a legal move, computed before anything is changed.
+struct Plan {
+    ship: usize,
+    after: Ship,
+    dock: Option<(usize, usize, usize)>,
+}
+
+impl State {
+    /// The start position on `board`: both ships in the first column of the first segment
+    /// with speed 1, 6 coal and one free turn, and team one to move.
+    pub fn start(board: Board) -> State {
+        let ship = |team, position| Ship {
+            team,
+            direction: CubeDirection::Right,
+            speed: 1,
+            coal: 6,
+            passengers: 0,
+            free_turns: 1,
+            points: 0,
+            position,
+        };
+        State {
+            class: "state".to_string(),
+            start_team: Team::One,
+            turn: 0,
+            current_team: Team::One,
+            board,
+            ship: vec![ship(Team::One, Coordinate::new(-1, -1)), ship(Team::Two, Coordinate::new(-2, 1))],
+        }
+    }
+
+    pub fn ship_of(&self, team: &Team) -> Option<&Ship> {
+        self.ship.iter().find(|ship| ship.team == *team)
+    }
+
+    pub fn legal_moves(&self) -> Vec<Move> {
+        let Some(ship) = self.ship_of(&self.current_team) else {
+            return Vec::new();
+        };
+        let mut moves = Vec::new();
+        for direction in CubeDirection::HEX {
+            let step = direction.vector().unwrap();
+            for distance in 1..=MAX_SPEED {
+                let m = Move { from: ship.position.clone(), to: ship.position.clone() + step.clone() * distance };
+                match self.plan(&m) {
+                    Ok(_) => moves.push(m),
+                    Err(RulesError::Blocked(_) | RulesError::TooFast(_)) => break,
+                    Err(_) => {}
+                }
+            }
+        }
+        moves
+    }
+
+    /// Applies a move and returns the record to take it back. On an error the state is unchanged.
+    pub fn make_move(&mut self, m: &Move) -> Result<Undo, RulesError> {
+        if self.turn >= MAX_TURNS {
+            return Err(RulesError::GameOver);
+        }
+        let plan = self.plan(m)?;
+        let undo = Undo {
+            ship: plan.ship,
+            before: std::mem::replace(&mut self.ship[plan.ship], plan.after),
+            dock: plan.dock,
+            turn: self.turn,
+            current_team: self.current_team.clone(),
+        };
+        if let Some((segment, column, row)) = plan.dock {
+            if let Field::Passenger(passenger) = &mut self.board.segment[segment].field_array[column].fields[row] {
+                passenger.passenger -= 1;
+            }
+        }
+        self.turn += 1;
+        self.current_team = self.next_team(&undo.before.team);
+        Ok(undo)
+    }
+
+    /// Takes back the move that returned `undo`. Moves must be taken back in reverse order.
+    pub fn unmake_move(&mut self, undo: Undo) {
+        if let Some((segment, column, row)) = undo.dock {
+            if let Field::Passenger(passenger) = &mut self.board.segment[segment].field_array[column].fields[row] {
+                passenger.passenger += 1;
+            }
+        }
+        self.ship[undo.ship] = undo.before;
+        self.turn = undo.turn;
+        self.current_team = undo.current_team;
+    }
+
+    /// Whether the ship stands on a goal field with enough passengers and slow enough to stop.
+    pub fn is_finished(&self, ship: &Ship) -> bool {
+        matches!(self.board.field_at(&ship.position), Some(Field::Goal))
+            && ship.passengers >= PASSENGERS_TO_FINISH
+            && self.can_stop(ship.speed, &ship.position)
+    }
+
+    /// The game ends after [`MAX_TURNS`], at the end of a round in which a ship finished,
+    /// or when the team to move has no legal move.
+    pub fn is_game_over(&self) -> bool {
+        self.turn >= MAX_TURNS
+            || (self.turn % 2 == 0 && self.ship.iter().any(|ship| self.is_finished(ship)))
+            || self.legal_moves().is_empty()
+    }
+
+    /// The winner of a finished game: the only finished ship, otherwise the team with more points.
+    pub fn winner(&self) -> Option<Team> {
+        let finished: Vec<&Ship> = self.ship.iter().filter(|ship| self.is_finished(ship)).collect();
+        if let [ship] = finished.as_slice() {
+            return Some(ship.team.clone());
+        }
+        let (one, two) = (self.ship_of(&Team::One)?, self.ship_of(&Team::Two)?);
+        match one.points.cmp(&two.points) {
+            std::cmp::Ordering::Greater => Some(Team::One),
+            std::cmp::Ordering::Less => Some(Team::Two),
+            std::cmp::Ordering::Equal => None,
+        }
+    }
+
+    fn plan(&self, m: &Move) -> Result<Plan, RulesError> {
+        let index = self
+            .ship
+            .iter()
+            .position(|ship| ship.team == self.current_team && ship.position == m.from)
+            .ok_or_else(|| RulesError::NotYourShip(m.from.clone()))?;
+        let ship = &self.ship[index];
+        let (direction, distance) = straight_line(&m.from, &m.to).ok_or_else(|| RulesError::NotStraight(m.to.clone()))?;
+        let step = direction.vector().unwrap();
+
+        let mut movement = 0;
+        let mut on_sandbank = false;
+        for i in 1..=distance {
+            let coordinate = m.from.clone() + step.clone() * i;
+            let blocked_by_ship = self.ship.iter().any(|other| other.position == coordinate);
+            match self.board.field_at(&coordinate) {
+                Some(Field::Water | Field::Goal) if !blocked_by_ship => {}
+                Some(Field::Sandbank) if !blocked_by_ship && i == distance => on_sandbank = true,
+                _ => return Err(RulesError::Blocked(coordinate)),
+            }
+            movement += 1 + self.board.has_current(&coordinate) as i32;
+        }
+        if movement > MAX_SPEED {
+            return Err(RulesError::TooFast(movement));
+        }
+
+        let acceleration_cost = ((movement - ship.speed).abs() - 1).max(0);
+        let turns = ship.direction.turns().map_or(0, |turns| {
+            let difference = (direction.turns().unwrap() - turns).rem_euclid(6);
+            difference.min(6 - difference)
+        });
+        let cost = acceleration_cost + (turns - ship.free_turns).max(0);
+        if cost > ship.coal {
+            return Err(RulesError::NotEnoughCoal(cost));
+        }
+
+        let mut after = ship.clone();
+        after.position = m.to.clone();
+        after.direction = direction;
+        after.speed = if on_sandbank { 1 } else { movement };
+        after.coal -= cost;
+        after.free_turns = 1;
+        let dock = self.dock_in_reach(&after);
+        if dock.is_some() {
+            after.passengers += 1;
+        }
+        after.points = self.points_of(&after);
+        Ok(Plan { ship: index, after, dock })
+    }
+
+    /// The first adjacent dock with a passenger that faces the ship, if the ship is slow enough.
+    fn dock_in_reach(&self, ship: &Ship) -> Option<(usize, usize, usize)> {
+        if !self.can_stop(ship.speed, &ship.position) {
+            return None;
+        }
+        CubeDirection::HEX.iter().find_map(|direction| {
+            let coordinate = ship.position.clone() + direction.vector().unwrap();
+            let segment = self.board.segment_index(&coordinate)?;
+            let (column, row) = self.board.segment[segment].index_of(&coordinate)?;
+            match &self.board.segment[segment].field_array[column].fields[row] {
+                Field::Passenger(passenger)
+                    if passenger.passenger > 0
+                        && passenger.direction.vector().map(|v| coordinate.clone() + v) == Some(ship.position.clone()) =>
+                {
+                    Some((segment, column, row))
+                }
+                _ => None,
+            }
+        })
+    }
+
+    fn can_stop(&self, speed: i32, position: &Coordinate) -> bool {
+        speed <= 1 || (speed == 2 && self.board.has_current(position))
+    }
+
+    fn points_of(&self, ship: &Ship) -> i32 {
+        let progress = self.board.segment_index(&ship.position).map_or(0, |segment| {
+            let (column, _) = self.board.segment[segment].index_of(&ship.position).unwrap();
+            POINTS_PER_SEGMENT * segment as i32 + column as i32
+        });
+        progress + POINTS_PER_PASSENGER * ship.passengers
+    }
+
+    /// Called after `turn` was advanced by a move of `mover`.
+    fn next_team(&self, mover: &Team) -> Team {
+        if self.turn % 2 == 1 {
+            return mover.opponent();
+        }
+        match (self.ship_of(&Team::One), self.ship_of(&Team::Two)) {
+            (Some(one), Some(two)) if one.points > two.points => Team::One,
+            (Some(one), Some(two)) if one.points < two.points => Team::Two,
+            _ => self.start_team.clone(),
+        }
+    }
+}
+
+/// Direction and number of steps from `from` to `to`, if they lie on a straight hex line.
+fn straight_line(from: &Coordinate, to: &Coordinate) -> Option<(CubeDirection, i32)> {
+    let distance = from.distance_to(to);
+    if distance == 0 {
+        return None;
+    }
+    CubeDirection::HEX
+        .into_iter()
+        .find(|direction| from.clone() + direction.vector().unwrap() * distance == *to)
+        .map(|direction| (direction, distance))
+}
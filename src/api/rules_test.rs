@@ -0,0 +1,136 @@
+#[cfg(test)]
+mod tests {
+    use crate::api::protocol::{Board, Coordinate, CubeDirection, Field, Move, State, Team};
+    use crate::api::rules::RulesError;
+    use crate::api::zobrist::ZobristHash;
+    use crate::random::Random;
+
+    fn state(board: &str) -> State {
+        State::from_notation(&format!("1 1 0 R R@0,0:{} 1R@-1,-1:1,6,0,1,0 2R@-2,1:1,6,0,1,0", board)).unwrap()
+    }
+
+    fn open_water() -> State {
+        state("wwwww|wwwww|wwwww|wwwww")
+    }
+
+    fn step(from: (i32, i32), to: (i32, i32)) -> Move {
+        Move { from: Coordinate::new(from.0, from.1), to: Coordinate::new(to.0, to.1) }
+    }
+
+    #[test]
+    fn test_start() {
+        let state = State::start(Board::generate(3));
+        assert_eq!(state.to_notation().split(' ').skip(5).collect::<Vec<_>>(), ["1R@-1,-1:1,6,0,1,0", "2R@-2,1:1,6,0,1,0"]);
+        assert_eq!(state.current_team, Team::One);
+        assert!(!state.legal_moves().is_empty());
+        assert!(!state.is_game_over());
+    }
+
+    #[test]
+    fn test_acceleration_and_turns() {
+        let mut state = open_water();
+        state.make_move(&step((-1, -1), (2, -1))).unwrap();
+        let ship = state.ship_of(&Team::One).unwrap();
+        assert_eq!((ship.speed, ship.coal, ship.points), (3, 5, 3));
+        assert_eq!(state.turn, 1);
+        assert_eq!(state.current_team, Team::Two);
+
+        // Two turns up left cost one coal beyond the free turn.
+        let mut state = open_water();
+        state.make_move(&step((-1, -1), (-1, -2))).unwrap();
+        let ship = state.ship_of(&Team::One).unwrap();
+        assert_eq!((ship.direction.clone(), ship.speed, ship.coal, ship.free_turns), (CubeDirection::UpLeft, 1, 5, 1));
+
+        // The first field down right has current and counts twice.
+        let mut state = open_water();
+        state.make_move(&step((-1, -1), (-1, 0))).unwrap();
+        assert_eq!(state.ship_of(&Team::One).unwrap().speed, 2);
+    }
+
+    #[test]
+    fn test_illegal_moves() {
+        let mut state = state("wwwww|wswww|wwwww|wwwww");
+        let before = state.clone();
+        let cases = [
+            (step((-2, 1), (-1, 1)), RulesError::NotYourShip(Coordinate::new(-2, 1))),
+            (step((-1, -1), (1, 0)), RulesError::NotStraight(Coordinate::new(1, 0))),
+            (step((-1, -1), (1, -1)), RulesError::Blocked(Coordinate::new(0, -1))),
+            (step((-1, -1), (-2, -1)), RulesError::Blocked(Coordinate::new(-2, -1))),
+        ];
+        for (m, error) in cases {
+            assert_eq!(state.make_move(&m), Err(error));
+            assert_eq!(state, before);
+        }
+
+        state.ship[0].coal = 0;
+        assert_eq!(state.make_move(&step((-1, -1), (-1, -2))), Err(RulesError::NotEnoughCoal(1)));
+        assert!(!state.legal_moves().contains(&step((-1, -1), (-1, -2))));
+
+        state.make_move(&step((-1, -1), (0, -1))).unwrap();
+        assert_eq!(state.ship_of(&Team::One).unwrap().speed, 1);
+    }
+
+    #[test]
+    fn test_passenger_pickup() {
+        let mut state = state("wwwww|pDR1wwww|wwwww|wwwww");
+        let before = state.clone();
+        assert_eq!(state.make_move(&step((-1, -1), (0, -2))), Err(RulesError::Blocked(Coordinate::new(0, -2))));
+
+        let mut hash = ZobristHash::of(&state);
+        let undo = state.make_move(&step((-1, -1), (0, -1))).unwrap();
+        hash.update(&state, &undo);
+        assert_eq!(hash, ZobristHash::of(&state));
+        let ship = state.ship_of(&Team::One).unwrap();
+        assert_eq!((ship.passengers, ship.points), (1, 6));
+        assert!(matches!(&state.board.segment[0].field_array[1].fields[0], Field::Passenger(dock) if dock.passenger == 0));
+
+        state.unmake_move(undo);
+        assert_eq!(state, before);
+    }
+
+    #[test]
+    fn test_leader_moves_first() {
+        let mut state = open_water();
+        state.make_move(&step((-1, -1), (0, -1))).unwrap();
+        state.make_move(&step((-2, 1), (0, 1))).unwrap();
+        assert_eq!(state.turn, 2);
+        assert_eq!(state.current_team, Team::Two);
+    }
+
+    #[test]
+    fn test_finish() {
+        let mut state = State::from_notation("1 1 0 R R@0,0:wwwww|wwwww|wwwww|ggggg 1R@1,-1:1,6,2,1,12 2R@-2,1:1,6,0,1,0").unwrap();
+        state.make_move(&step((1, -1), (2, -1))).unwrap();
+        assert!(!state.is_game_over());
+        state.make_move(&step((-2, 1), (-1, 1))).unwrap();
+        assert!(state.is_game_over());
+        assert_eq!(state.winner(), Some(Team::One));
+    }
+
+    #[test]
+    fn test_random_games_unmake_to_start() {
+        for seed in 0..20 {
+            let start = State::start(Board::generate(seed));
+            let mut state = start.clone();
+            let mut hash = ZobristHash::of(&state);
+            let mut random = Random::new(seed);
+            let mut history = Vec::new();
+
+            while !state.is_game_over() {
+                let moves = state.legal_moves();
+                let undo = state.make_move(&moves[random.below(moves.len())]).unwrap();
+                hash.update(&state, &undo);
+                assert_eq!(hash, ZobristHash::of(&state), "seed {} turn {}", seed, state.turn);
+                history.push(undo);
+            }
+            assert!(!history.is_empty());
+
+            while let Some(undo) = history.pop() {
+                hash.update(&state, &undo);
+                state.unmake_move(undo);
+                assert_eq!(hash, ZobristHash::of(&state));
+            }
+            assert_eq!(state, start);
+        }
+    }
+}
@@ -0,0 +1,145 @@
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use serde::de::DeserializeOwned;
+    use serde::Serialize;
+    use serde_json::json;
+
+    use crate::api::protocol::{
+        Coordinate, CubeDirection, Data, DataClass, ErrorPacket, Field, FieldArray, Move, OriginalRequest, Passenger, Room, Scores, Ship,
+        State, Team,
+    };
+    use crate::search::tournament::{GameRecord, Outcome, TournamentResult};
+
+    fn round_trip<T: Serialize + DeserializeOwned + PartialEq + std::fmt::Debug>(value: &T) -> serde_json::Value {
+        let json = serde_json::to_value(value).unwrap();
+        assert_eq!(&serde_json::from_value::<T>(json.clone()).unwrap(), value);
+        json
+    }
+
+    fn ship() -> Ship {
+        Ship {
+            team: Team::Two,
+            direction: CubeDirection::DownRight,
+            speed: 2,
+            coal: 5,
+            passengers: 1,
+            free_turns: 1,
+            points: 7,
+            position: Coordinate::new(-1, 2),
+        }
+    }
+
+    #[test]
+    fn test_ship() {
+        assert_eq!(
+            round_trip(&ship()),
+            json!({
+                "team": "TWO",
+                "direction": "DOWN_RIGHT",
+                "speed": 2,
+                "coal": 5,
+                "passengers": 1,
+                "freeTurns": 1,
+                "points": 7,
+                "position": { "q": -1, "r": 2, "s": -1 }
+            })
+        );
+    }
+
+    #[test]
+    fn test_fields() {
+        let column = FieldArray {
+            fields: vec![
+                Field::Water,
+                Field::Island,
+                Field::Sandbank,
+                Field::Passenger(Passenger { direction: CubeDirection::UpLeft, passenger: 1 }),
+                Field::Goal,
+            ],
+        };
+        assert_eq!(
+            round_trip(&column),
+            json!([
+                { "type": "water" },
+                { "type": "island" },
+                { "type": "sandbank" },
+                { "type": "passenger", "direction": "UP_LEFT", "passenger": 1 },
+                { "type": "goal" }
+            ])
+        );
+    }
+
+    #[test]
+    fn test_state() {
+        let state = State::from_notation("1 2 3 DR R@0,0:wwwww|wiwww|wwsww|pL1wwww 1R@-1,-1:1,6,0,1,0 2DR@-1,2:2,5,1,1,7").unwrap();
+        let json = round_trip(&state);
+        assert_eq!(json["class"], "state");
+        assert_eq!(json["startTeam"], "ONE");
+        assert_eq!(json["currentTeam"], "TWO");
+        assert_eq!(json["turn"], 3);
+        assert_eq!(json["board"]["nextDirection"], "DOWN_RIGHT");
+        assert_eq!(json["board"]["segment"][0]["direction"], "RIGHT");
+        assert_eq!(json["board"]["segment"][0]["center"], json!({ "q": 0, "r": 0, "s": 0 }));
+        assert_eq!(json["board"]["segment"][0]["fieldArray"][1][1], json!({ "type": "island" }));
+        assert_eq!(json["ship"][1], serde_json::to_value(ship()).unwrap());
+    }
+
+    #[test]
+    fn test_room() {
+        let room = Room {
+            room_id: "room-1".to_string(),
+            data: Data::new_move(Move { from: Coordinate::new(0, 0), to: Coordinate::new(1, 0) }),
+        };
+        assert_eq!(
+            round_trip(&room),
+            json!({
+                "roomId": "room-1",
+                "data": {
+                    "class": "move",
+                    "color": null,
+                    "state": null,
+                    "move": { "from": { "q": 0, "r": 0, "s": 0 }, "to": { "q": 1, "r": 0, "s": -1 } },
+                    "definition": null,
+                    "scores": null,
+                    "winner": null
+                }
+            })
+        );
+        assert_eq!(serde_json::to_value(DataClass::MoveRequest).unwrap(), "moveRequest");
+    }
+
+    #[test]
+    fn test_scores() {
+        let xml = r#"<scores><entry><player name="bot" team="ONE"/><score cause="REGULAR" reason=""><part>2</part><part>6</part></score></entry></scores>"#;
+        let scores: Scores = yaserde::de::from_str(xml).unwrap();
+        let json = round_trip(&scores);
+        assert_eq!(json["entries"][0]["player"], json!({ "name": "bot", "team": "ONE" }));
+        assert_eq!(json["entries"][0]["score"]["cause"], "REGULAR");
+    }
+
+    #[test]
+    fn test_error_packet() {
+        let error = ErrorPacket {
+            message: "Unknown reservation code".to_string(),
+            original_request: Some(OriginalRequest::JoinPrepared { reservation_code: "abc".to_string() }),
+        };
+        assert_eq!(
+            round_trip(&error),
+            json!({ "message": "Unknown reservation code", "originalRequest": { "class": "joinPrepared", "reservationCode": "abc" } })
+        );
+
+        let moved = OriginalRequest::Move(Move { from: Coordinate::new(0, 0), to: Coordinate::new(1, 0) });
+        assert_eq!(round_trip(&moved)["class"], "move");
+        assert_eq!(round_trip(&OriginalRequest::Join), json!({ "class": "join" }));
+        assert_eq!(round_trip(&OriginalRequest::Other("observe".to_string())), json!("observe"));
+    }
+    #[test]
+    fn test_tournament_result() {
+        let game = GameRecord { index: 1, seed: 7, candidate_first: false, outcome: Outcome::Draw, plies: 9 };
+        let json = round_trip(&TournamentResult { games: vec![game], sprt: None });
+        assert_eq!(json, json!({"games": [{"game": 1, "seed": 7, "candidateFirst": false, "outcome": "draw", "plies": 9}], "sprt": null}));
+
+        let report: serde_json::Value = serde_json::from_str(&TournamentResult { games: Vec::new(), sprt: None }.to_json()).unwrap();
+        assert!(report.get("eloError").is_some());
+    }
+}
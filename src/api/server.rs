@@ -0,0 +1,351 @@
+use std::collections::HashMap;
+use std::io::{self, Read, Write};
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use yaserde::YaSerialize;
+
+use crate::api::protocol::{
+    to_xml, Board, Data, DataClass, Definition, Entry, ErrorPacket, Fragment, Joined, Left, Move, OriginalRequest,
+    Player as PlayerInfo, ProtocolPacket, Room, Score, Scores, State, Team,
+};
+use crate::api::result::ScoreCause;
+use crate::api::xml_buffer::XmlBuffer;
+
+/// How long a finished game waits for the clients to close their connections.
+const CLOSE_TIMEOUT: Duration = Duration::from_secs(1);
+
+#[derive(Clone, Debug)]
+pub struct ServerConfig {
+    pub port: u16,
+    /// Board seed of the first game, every further game adds one.
+    pub seed: u64,
+    /// How long a player may think about a move before losing by a hard timeout.
+    pub move_timeout: Duration,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        ServerConfig { port: 13050, seed: 0, move_timeout: Duration::from_secs(2) }
+    }
+}
+
+/// A local game server that speaks the TCP/XML protocol of the Software-Challenge server.
+///
+/// Clients join with `<join/>` into the first open room, with `<joinRoom>` into the room with
+/// that id, which is opened if it does not exist, or with `<joinPrepared>` into a seat of
+/// [`Server::prepare_room`]. The first seat plays team one. A full room starts the game on the
+/// board [`Board::generate`] creates for the room's seed. Moves are checked by the rules
+/// engine; an illegal move, a timeout or a closed connection ends the game with the opponent
+/// as the winner.
+pub struct Server {
+    listener: TcpListener,
+    config: ServerConfig,
+    lobby: Arc<Mutex<Lobby>>,
+}
+
+impl Server {
+    /// Listens on localhost. Port 0 picks a free port, see [`Server::port`].
+    pub fn bind(config: ServerConfig) -> io::Result<Server> {
+        let listener = TcpListener::bind(("127.0.0.1", config.port))?;
+        Ok(Server { listener, config, lobby: Arc::new(Mutex::new(Lobby::default())) })
+    }
+
+    pub fn port(&self) -> u16 {
+        self.listener.local_addr().map_or(self.config.port, |address| address.port())
+    }
+
+    /// Opens a room whose seats can only be taken with the returned reservation codes, the
+    /// first one for team one.
+    pub fn prepare_room(&self) -> (String, [String; 2]) {
+        let mut lobby = self.lobby.lock().unwrap();
+        let room_id = lobby.open_room(None, self.config.seed);
+        let codes = [Team::One, Team::Two].map(|team| format!("{}-{}", room_id, team));
+        for (seat, code) in codes.iter().enumerate() {
+            lobby.reservations.insert(code.clone(), (room_id.clone(), seat));
+        }
+        lobby.rooms.get_mut(&room_id).unwrap().prepared = true;
+        (room_id, codes)
+    }
+
+    /// Accepts clients until the listener fails. Every connection waits for its join request
+    /// on its own thread, and the game of a full room runs on the thread of the second player.
+    pub fn run(&self) -> io::Result<()> {
+        loop {
+            let (stream, _) = self.listener.accept()?;
+            let lobby = Arc::clone(&self.lobby);
+            let config = self.config.clone();
+            thread::spawn(move || {
+                if let Err(error) = connect(stream, &lobby, &config) {
+                    eprintln!("Connection failed: {}", error);
+                }
+            });
+        }
+    }
+}
+
+/// Rooms that wait for players.
+#[derive(Default)]
+struct Lobby {
+    games: u64,
+    rooms: HashMap<String, OpenRoom>,
+    /// Room id and seat of every reservation code that was not used yet.
+    reservations: HashMap<String, (String, usize)>,
+}
+
+struct OpenRoom {
+    seed: u64,
+    seats: [Option<Player>; 2],
+    /// Only reservation codes can take the seats.
+    prepared: bool,
+}
+
+/// A room that was full and left the lobby.
+struct Game {
+    room_id: String,
+    seed: u64,
+    players: [Player; 2],
+}
+
+impl Lobby {
+    fn open_room(&mut self, room_id: Option<String>, seed: u64) -> String {
+        self.games += 1;
+        let room_id = room_id.unwrap_or_else(|| format!("room-{}", self.games));
+        let room = OpenRoom { seed: seed + self.games - 1, seats: [None, None], prepared: false };
+        self.rooms.insert(room_id.clone(), room);
+        room_id
+    }
+
+    /// The room and seat for a join request, or the message of the error packet.
+    fn find_seat(&mut self, request: &OriginalRequest, seed: u64) -> Result<(String, usize), String> {
+        let free_seat = |room: &OpenRoom| room.seats.iter().position(Option::is_none);
+        match request {
+            OriginalRequest::JoinPrepared { reservation_code } => {
+                self.reservations.remove(reservation_code).ok_or_else(|| "Unknown reservation code".to_string())
+            }
+            OriginalRequest::JoinRoom { room_id } => match self.rooms.get(room_id) {
+                Some(room) if !room.prepared => {
+                    free_seat(room).map(|seat| (room_id.clone(), seat)).ok_or_else(|| format!("Room {} is full", room_id))
+                }
+                Some(_) => Err(format!("Room {} needs a reservation code", room_id)),
+                None => Ok((self.open_room(Some(room_id.clone()), seed), 0)),
+            },
+            _ => {
+                let mut open: Vec<(&String, &OpenRoom)> = self.rooms.iter().filter(|(_, room)| !room.prepared).collect();
+                open.sort_by_key(|(_, room)| room.seed);
+                match open.first() {
+                    Some((room_id, room)) => Ok(((*room_id).clone(), free_seat(room).unwrap())),
+                    None => Ok((self.open_room(None, seed), 0)),
+                }
+            }
+        }
+    }
+
+    /// Seats the player and returns the game once the room is full.
+    fn take_seat(&mut self, room_id: &str, seat: usize, player: Player) -> Option<Game> {
+        let room = self.rooms.get_mut(room_id)?;
+        room.seats[seat] = Some(player);
+        if room.seats.iter().any(Option::is_none) {
+            return None;
+        }
+        let room = self.rooms.remove(room_id)?;
+        let [Some(one), Some(two)] = room.seats else {
+            return None;
+        };
+        Some(Game { room_id: room_id.to_string(), seed: room.seed, players: [one, two] })
+    }
+}
+
+fn connect(stream: TcpStream, lobby: &Mutex<Lobby>, config: &ServerConfig) -> io::Result<()> {
+    // Every turn writes a memento and a small move request back to back.
+    stream.set_nodelay(true)?;
+    let mut player = Player::new(stream);
+    player.stream.write_all(b"<protocol>\n")?;
+    let request = match player.receive(None)? {
+        ProtocolPacket::Join(_) => OriginalRequest::Join,
+        ProtocolPacket::JoinRoom(join) => OriginalRequest::JoinRoom { room_id: join.room_id },
+        ProtocolPacket::JoinPrepared(join) => OriginalRequest::JoinPrepared { reservation_code: join.reservation_code },
+        packet => {
+            let message = format!("Expected a join request, got {:?}", packet);
+            return player.send(&ErrorPacket { message, original_request: None });
+        }
+    };
+
+    let game = {
+        let mut lobby = lobby.lock().unwrap();
+        let (room_id, seat) = match lobby.find_seat(&request, config.seed) {
+            Ok(seat) => seat,
+            Err(message) => return player.send(&ErrorPacket { message, original_request: Some(request) }),
+        };
+        player.send(&Joined { room_id: room_id.clone() })?;
+        lobby.take_seat(&room_id, seat, player)
+    };
+    if let Some(game) = game {
+        play(game, config);
+    }
+    Ok(())
+}
+
+/// How a game ended, with the team that caused an early end.
+enum Ending {
+    Regular,
+    Early(Team, ScoreCause, String),
+}
+
+fn play(mut game: Game, config: &ServerConfig) {
+    let room_id = game.room_id.clone();
+    let mut state = State::start(Board::generate(game.seed));
+    println!("Room {}: game started with seed {}", room_id, game.seed);
+
+    let ending = (|| {
+        for team in [Team::One, Team::Two] {
+            let data = Data { class: DataClass::WelcomeMessage, color: Some(team.to_string()), ..Default::default() };
+            game.send_to(&team, data)?;
+        }
+        loop {
+            for team in [Team::One, Team::Two] {
+                game.send_to(&team, Data { class: DataClass::Memento, state: Some(state.clone()), ..Default::default() })?;
+            }
+            if state.is_game_over() {
+                return Ok(());
+            }
+            let team = state.current_team.clone();
+            game.send_to(&team, Data { class: DataClass::MoveRequest, ..Default::default() })?;
+            let m = game.receive_move(&team, config.move_timeout)?;
+            if let Err(error) = state.make_move(&m) {
+                return Err(Ending::Early(team, ScoreCause::RuleViolation, error.to_string()));
+            }
+        }
+    })()
+    .err()
+    .unwrap_or(Ending::Regular);
+
+    let data = result(&state, &ending);
+    println!("Room {}: game over, winner {:?}", room_id, data.winner.as_ref().map(|winner| winner.team()));
+    for player in game.players.iter_mut() {
+        let _ = player.send(&Room { room_id: room_id.clone(), data: data.clone() });
+        let _ = player.send(&Left { room_id: room_id.clone() });
+    }
+    for player in game.players {
+        player.close();
+    }
+}
+
+impl Game {
+    /// Sends a room packet. A failed write ends the game as if the player had left.
+    fn send_to(&mut self, team: &Team, data: Data) -> Result<(), Ending> {
+        let room = Room { room_id: self.room_id.clone(), data };
+        self.players[seat_of(team)].send(&room).map_err(|error| Ending::Early(team.clone(), ScoreCause::Left, error.to_string()))
+    }
+
+    /// Waits for the move of `team`, skipping other packets.
+    fn receive_move(&mut self, team: &Team, timeout: Duration) -> Result<Move, Ending> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            match self.players[seat_of(team)].receive(Some(deadline)) {
+                Ok(ProtocolPacket::Room(Room { data: Data { _move: Some(m), .. }, .. })) => return Ok(m),
+                Ok(ProtocolPacket::Close(_)) => {
+                    return Err(Ending::Early(team.clone(), ScoreCause::Left, "The player left the game".to_string()))
+                }
+                Ok(_) => {}
+                Err(error) if error.kind() == io::ErrorKind::TimedOut => {
+                    let reason = format!("No move within {} ms", timeout.as_millis());
+                    return Err(Ending::Early(team.clone(), ScoreCause::HardTimeout, reason));
+                }
+                Err(error) => return Err(Ending::Early(team.clone(), ScoreCause::Left, error.to_string())),
+            }
+        }
+    }
+}
+
+fn seat_of(team: &Team) -> usize {
+    match team {
+        Team::One => 0,
+        Team::Two => 1,
+    }
+}
+
+/// The result packet: two Siegpunkte for the winner and one each for a draw, and the points of
+/// the ships. After an early end the opponent of the causing team wins.
+fn result(state: &State, ending: &Ending) -> Data {
+    let winner = match ending {
+        Ending::Regular => state.winner(),
+        Ending::Early(team, _, _) => Some(team.opponent()),
+    };
+    let entries = [Team::One, Team::Two]
+        .into_iter()
+        .map(|team| {
+            let victory_points = match &winner {
+                Some(winner) if *winner == team => 2,
+                Some(_) => 0,
+                None => 1,
+            };
+            let points = state.ship_of(&team).map_or(0, |ship| ship.points);
+            let score = match ending {
+                Ending::Early(causer, cause, reason) if *causer == team => Score::new(*cause, reason.clone(), vec![victory_points, points]),
+                _ => Score::new(ScoreCause::Regular, String::new(), vec![victory_points, points]),
+            };
+            Entry::new(PlayerInfo::new(team.to_string(), team), score)
+        })
+        .collect();
+    let definition = Definition::new(vec![
+        Fragment::new("Siegpunkte".to_string(), "SUM".to_string(), true),
+        Fragment::new("Punkte".to_string(), "AVERAGE".to_string(), true),
+    ]);
+    Data::new_result(definition, Scores::new(entries), winner)
+}
+
+/// The connection of one client.
+struct Player {
+    stream: TcpStream,
+    buffer: XmlBuffer,
+}
+
+impl Player {
+    fn new(stream: TcpStream) -> Self {
+        Player { stream, buffer: XmlBuffer::new() }
+    }
+
+    fn send<T: YaSerialize>(&mut self, packet: &T) -> io::Result<()> {
+        let xml = to_xml(packet).map_err(io::Error::other)?;
+        self.stream.write_all(xml.as_bytes())
+    }
+
+    /// Reads the next packet. Fails with [`io::ErrorKind::TimedOut`] once the deadline passed.
+    fn receive(&mut self, deadline: Option<Instant>) -> io::Result<ProtocolPacket> {
+        loop {
+            if let Some(xml) = self.buffer.pop_xml() {
+                return ProtocolPacket::parse(&String::from_utf8_lossy(&xml)).map_err(io::Error::other);
+            }
+            let timeout = match deadline {
+                Some(deadline) => match deadline.checked_duration_since(Instant::now()) {
+                    Some(remaining) if !remaining.is_zero() => Some(remaining),
+                    _ => return Err(io::Error::from(io::ErrorKind::TimedOut)),
+                },
+                None => None,
+            };
+            self.stream.set_read_timeout(timeout)?;
+            let mut buf = [0; 4096];
+            let bytes_read = match self.stream.read(&mut buf) {
+                Ok(bytes_read) => bytes_read,
+                Err(error) if error.kind() == io::ErrorKind::WouldBlock => continue,
+                Err(error) => return Err(error),
+            };
+            if bytes_read == 0 {
+                return Err(io::Error::from(io::ErrorKind::UnexpectedEof));
+            }
+            self.buffer.push(&buf[..bytes_read]);
+        }
+    }
+
+    /// Ends the own side of the connection and waits until the client closed its side, so a
+    /// late packet of the client does not reset the connection before it read the result.
+    fn close(mut self) {
+        let _ = self.stream.shutdown(Shutdown::Write);
+        let _ = self.stream.set_read_timeout(Some(CLOSE_TIMEOUT));
+        let mut buf = [0; 4096];
+        while matches!(self.stream.read(&mut buf), Ok(bytes_read) if bytes_read > 0) {}
+    }
+}
@@ -0,0 +1,145 @@
+#[cfg(test)]
+mod tests {
+    use std::io::{Read, Write};
+    use std::net::TcpStream;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+    use std::time::Duration;
+
+    use crate::api::game_client::{ClientInterface, GameClient, GameContext};
+    use crate::api::protocol::{ErrorPacket, Joined, Left, Move, OriginalRequest, ProtocolPacket, Room, State, Team};
+    use crate::api::result::{GameResult, ScoreCause};
+    use crate::api::server::{Server, ServerConfig};
+    use crate::api::xml_buffer::XmlBuffer;
+
+    #[derive(Clone, Copy)]
+    enum Behaviour {
+        Legal,
+        Illegal,
+        Slow(Duration),
+    }
+
+    /// Plays the first legal move of the last state and keeps the result.
+    struct Bot {
+        behaviour: Behaviour,
+        state: Option<State>,
+        result: Arc<Mutex<Option<GameResult>>>,
+    }
+
+    impl ClientInterface for Bot {
+        fn on_state(&mut self, state: Room, _context: &GameContext) {
+            self.state = state.data.state;
+        }
+
+        fn on_move_request(&mut self, _context: &GameContext) -> Move {
+            match self.behaviour {
+                Behaviour::Illegal => return Move::default(),
+                Behaviour::Slow(duration) => thread::sleep(duration),
+                Behaviour::Legal => {}
+            }
+            self.state.as_ref().unwrap().legal_moves()[0].clone()
+        }
+
+        fn on_error_packet(&mut self, _error_packet: ErrorPacket) {}
+
+        fn on_erroneous_move(&mut self, _error: Room, _context: &GameContext) {}
+
+        fn on_joined(&mut self, _joined: Joined) {}
+
+        fn on_welcome(&mut self, _welcome_message: Room, _context: &GameContext) {}
+
+        fn on_left(&mut self, _left: Left) {}
+
+        fn on_result(&mut self, result: Room, _context: &GameContext) {
+            *self.result.lock().unwrap() = GameResult::from_data(&result.data);
+        }
+    }
+
+    fn start(move_timeout: Duration) -> Arc<Server> {
+        let server = Arc::new(Server::bind(ServerConfig { port: 0, seed: 3, move_timeout }).unwrap());
+        let running = Arc::clone(&server);
+        thread::spawn(move || running.run());
+        server
+    }
+
+    /// Plays one game with a client per join and returns the team and result of each client.
+    fn play(server: &Server, joins: [(Option<String>, Option<String>); 2], behaviours: [Behaviour; 2]) -> Vec<(Team, GameResult)> {
+        let port = server.port();
+        let clients: Vec<_> = joins
+            .into_iter()
+            .zip(behaviours)
+            .map(|((reservation_code, room_id), behaviour)| {
+                thread::spawn(move || {
+                    let result = Arc::new(Mutex::new(None));
+                    let bot = Bot { behaviour, state: None, result: Arc::clone(&result) };
+                    let mut client = GameClient::new("127.0.0.1".to_string(), port, reservation_code, room_id, Box::new(bot)).unwrap();
+                    client.run().unwrap();
+                    let result = result.lock().unwrap().take().expect("the client received a result");
+                    (client.my_team().unwrap(), result)
+                })
+            })
+            .collect();
+        clients.into_iter().map(|client| client.join().unwrap()).collect()
+    }
+
+    #[test]
+    fn test_game_between_two_clients() {
+        let server = start(Duration::from_secs(5));
+        let games = play(&server, [(None, None), (None, None)], [Behaviour::Legal; 2]);
+
+        assert_ne!(games[0].0, games[1].0);
+        assert_eq!(games[0].1, games[1].1);
+        let result = &games[0].1;
+        assert!(result.is_regular());
+        let victory_points: i32 = result.players.iter().map(|player| player.part("Siegpunkte").unwrap()).sum();
+        assert_eq!(victory_points, 2);
+    }
+
+    #[test]
+    fn test_rule_violation_and_timeout() {
+        let server = start(Duration::from_secs(5));
+        let games = play(&server, [(None, None), (None, None)], [Behaviour::Legal, Behaviour::Illegal]);
+        let (team, result) = &games[1];
+        assert_eq!(result.player(team).unwrap().cause, ScoreCause::RuleViolation);
+        assert_eq!(result.winner, Some(team.opponent()));
+
+        let server = start(Duration::from_millis(200));
+        let games = play(&server, [(None, None), (None, None)], [Behaviour::Legal, Behaviour::Slow(Duration::from_millis(600))]);
+        let (team, result) = &games[1];
+        assert_eq!(result.player(team).unwrap().cause, ScoreCause::HardTimeout);
+        assert_eq!(result.winner, Some(team.opponent()));
+    }
+
+    #[test]
+    fn test_join_room_and_reservation() {
+        let server = start(Duration::from_secs(5));
+        let room = || (None, Some("abc".to_string()));
+        let games = play(&server, [room(), room()], [Behaviour::Legal; 2]);
+        assert_ne!(games[0].0, games[1].0);
+
+        let (_, [one, two]) = server.prepare_room();
+        let games = play(&server, [(Some(two), None), (Some(one), None)], [Behaviour::Legal; 2]);
+        assert_eq!(games[0].0, Team::Two);
+        assert_eq!(games[1].0, Team::One);
+    }
+
+    #[test]
+    fn test_unknown_reservation_code() {
+        let server = start(Duration::from_secs(5));
+        let mut stream = TcpStream::connect(("127.0.0.1", server.port())).unwrap();
+        stream.write_all(br#"<protocol><joinPrepared reservationCode="nope" />"#).unwrap();
+        let mut received = Vec::new();
+        stream.read_to_end(&mut received).unwrap();
+
+        let mut buffer = XmlBuffer::new();
+        buffer.push(&received);
+        let packet = ProtocolPacket::parse(&String::from_utf8_lossy(&buffer.pop_xml().unwrap())).unwrap();
+        match packet {
+            ProtocolPacket::ErrorPacket(error) => {
+                assert_eq!(error.message, "Unknown reservation code");
+                assert_eq!(error.original_request, Some(OriginalRequest::JoinPrepared { reservation_code: "nope".to_string() }));
+            }
+            _ => panic!("Expected ErrorPacket"),
+        }
+    }
+}
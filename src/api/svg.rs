@@ -0,0 +1,134 @@
+use std::fmt::Write;
+
+use crate::api::protocol::{Coordinate, CubeDirection, Field, Ship, State, Team};
+
+/// Distance from the center of a hex to its corners, in SVG user units.
+const HEX_SIZE: f64 = 30.0;
+
+const MARGIN: f64 = 10.0;
+
+impl State {
+    /// Draws all revealed segments and both ships as a standalone SVG document.
+    pub fn to_svg(&self) -> String {
+        let mut centers: Vec<(f64, f64)> = self.board.fields().map(|(coordinate, _)| pixel(&coordinate)).collect();
+        centers.extend(self.ship.iter().map(|ship| pixel(&ship.position)));
+
+        let min_x = centers.iter().map(|c| c.0).fold(0.0, f64::min) - HEX_SIZE - MARGIN;
+        let max_x = centers.iter().map(|c| c.0).fold(0.0, f64::max) + HEX_SIZE + MARGIN;
+        let min_y = centers.iter().map(|c| c.1).fold(0.0, f64::min) - HEX_SIZE - MARGIN - 20.0;
+        let max_y = centers.iter().map(|c| c.1).fold(0.0, f64::max) + HEX_SIZE + MARGIN;
+        let (width, height) = (max_x - min_x, max_y - min_y);
+
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{:.0}" height="{:.0}" viewBox="{:.1} {:.1} {:.1} {:.1}">"#,
+            width, height, min_x, min_y, width, height
+        );
+        let _ = writeln!(svg, r##"<rect x="{:.1}" y="{:.1}" width="{:.1}" height="{:.1}" fill="#f4f1e8"/>"##, min_x, min_y, width, height);
+        let _ = writeln!(
+            svg,
+            r#"<text x="{:.1}" y="{:.1}" font-family="sans-serif" font-size="14">Turn {}, {} to move</text>"#,
+            min_x + MARGIN,
+            min_y + MARGIN + 14.0,
+            self.turn,
+            self.current_team
+        );
+
+        for (coordinate, field) in self.board.fields() {
+            self.write_field(&mut svg, &coordinate, field);
+        }
+        for ship in &self.ship {
+            write_ship(&mut svg, ship);
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    fn write_field(&self, svg: &mut String, coordinate: &Coordinate, field: &Field) {
+        let (x, y) = pixel(coordinate);
+        let fill = match field {
+            Field::Water if self.board.has_current(coordinate) => "#2b6cb0",
+            Field::Water => "#63b3ed",
+            Field::Island => "#5a8f3c",
+            Field::Sandbank => "#e9d18b",
+            Field::Passenger(_) => "#dd8a3c",
+            Field::Goal => "#c53030",
+        };
+        let _ = writeln!(svg, r##"<polygon points="{}" fill="{}" stroke="#1a365d" stroke-width="1"/>"##, hex_corners(x, y), fill);
+
+        if let Field::Passenger(passenger) = field {
+            let (dx, dy) = unit_vector(&passenger.direction);
+            let _ = writeln!(
+                svg,
+                r##"<line x1="{:.1}" y1="{:.1}" x2="{:.1}" y2="{:.1}" stroke="#1a202c" stroke-width="4"/>"##,
+                x,
+                y,
+                x + dx * HEX_SIZE * 0.85,
+                y + dy * HEX_SIZE * 0.85
+            );
+            let _ = writeln!(
+                svg,
+                r#"<text x="{:.1}" y="{:.1}" font-family="sans-serif" font-size="14" font-weight="bold" text-anchor="middle">{}</text>"#,
+                x,
+                y - 6.0,
+                passenger.passenger
+            );
+        }
+
+        let _ = writeln!(
+            svg,
+            r##"<text x="{:.1}" y="{:.1}" font-family="sans-serif" font-size="8" text-anchor="middle" fill="#1a202c">{},{}</text>"##,
+            x,
+            y + HEX_SIZE * 0.6,
+            coordinate.q,
+            coordinate.r
+        );
+    }
+}
+
+fn write_ship(svg: &mut String, ship: &Ship) {
+    let (x, y) = pixel(&ship.position);
+    let (dx, dy) = unit_vector(&ship.direction);
+    let (nx, ny) = (-dy, dx);
+    let fill = match ship.team {
+        Team::One => "#e53e3e",
+        Team::Two => "#3182ce",
+    };
+    let tip = (x + dx * HEX_SIZE * 0.7, y + dy * HEX_SIZE * 0.7);
+    let left = (x - dx * HEX_SIZE * 0.4 + nx * HEX_SIZE * 0.4, y - dy * HEX_SIZE * 0.4 + ny * HEX_SIZE * 0.4);
+    let right = (x - dx * HEX_SIZE * 0.4 - nx * HEX_SIZE * 0.4, y - dy * HEX_SIZE * 0.4 - ny * HEX_SIZE * 0.4);
+    let _ = writeln!(
+        svg,
+        r##"<polygon points="{:.1},{:.1} {:.1},{:.1} {:.1},{:.1}" fill="{}" stroke="#1a202c" stroke-width="2"><title>{}</title></polygon>"##,
+        tip.0, tip.1, left.0, left.1, right.0, right.1, fill, ship
+    );
+}
+
+/// Center of a hex in SVG coordinates, with pointy-top hexes and `r` growing downwards.
+fn pixel(coordinate: &Coordinate) -> (f64, f64) {
+    let q = coordinate.q as f64;
+    let r = coordinate.r as f64;
+    (HEX_SIZE * 3f64.sqrt() * (q + r / 2.0), HEX_SIZE * 1.5 * r)
+}
+
+fn hex_corners(x: f64, y: f64) -> String {
+    (0..6)
+        .map(|i| {
+            let angle = (60.0 * i as f64 - 30.0).to_radians();
+            format!("{:.1},{:.1}", x + HEX_SIZE * angle.cos(), y + HEX_SIZE * angle.sin())
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn unit_vector(direction: &CubeDirection) -> (f64, f64) {
+    let (x, y) = match direction.vector() {
+        Some(vector) => pixel(&vector),
+        None if *direction == CubeDirection::Up => (0.0, -1.0),
+        None => (0.0, 1.0),
+    };
+    let length = (x * x + y * y).sqrt();
+    (x / length, y / length)
+}
@@ -0,0 +1,52 @@
+#[cfg(test)]
+mod tests {
+    use yaserde::__xml::reader::{EventReader, XmlEvent};
+
+    use crate::api::protocol::{Board, Coordinate, CubeDirection, Field, FieldArray, Passenger, Segment, Ship, State, Team};
+
+    fn state() -> State {
+        let mut columns: Vec<FieldArray> = (0..4).map(|_| FieldArray { fields: vec![Field::Water; 5] }).collect();
+        columns[2].fields[0] = Field::Island;
+        columns[1].fields[0] = Field::Passenger(Passenger { direction: CubeDirection::DownRight, passenger: 1 });
+        columns[3].fields = vec![Field::Goal; 5];
+        State {
+            class: "state".to_string(),
+            start_team: Team::One,
+            turn: 12,
+            current_team: Team::One,
+            board: Board {
+                next_direction: CubeDirection::Right,
+                segment: vec![Segment { direction: CubeDirection::Right, center: Coordinate::new(0, 0), field_array: columns }],
+            },
+            ship: vec![
+                Ship { team: Team::One, direction: CubeDirection::Right, speed: 1, coal: 6, position: Coordinate::new(-1, -1), ..Default::default() },
+                Ship { team: Team::Two, direction: CubeDirection::DownLeft, speed: 1, coal: 6, position: Coordinate::new(-2, 1), ..Default::default() },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_svg_is_well_formed() {
+        let svg = state().to_svg();
+        let mut elements = Vec::new();
+        for event in EventReader::new(svg.as_bytes()) {
+            if let XmlEvent::StartElement { name, .. } = event.unwrap() {
+                elements.push(name.local_name);
+            }
+        }
+        assert_eq!(elements[0], "svg");
+        // 20 fields plus one polygon per ship
+        assert_eq!(elements.iter().filter(|name| *name == "polygon").count(), 22);
+        assert_eq!(elements.iter().filter(|name| *name == "line").count(), 1);
+    }
+
+    #[test]
+    fn test_svg_content() {
+        let svg = state().to_svg();
+        assert!(svg.contains("Turn 12, ONE to move"));
+        assert!(svg.contains(">-1,-1</text>"));
+        assert!(svg.contains(r##"fill="#c53030""##));
+        assert!(svg.contains(r##"fill="#5a8f3c""##));
+        assert!(svg.contains("<title>TWO at (-2, 1, 1)"));
+    }
+}
@@ -14,7 +14,9 @@ impl XmlBuffer {
     }
 
     pub fn pop_xml(&mut self) -> Option<Vec<u8>> {
-        let regex_str: &str = r#"<((room[\s\S]+?</room>)|errorpacket[\s\S]+?</errorpacket>|.*?/>)"#;
+        // A self-closing packet can not contain other tags, so an opening `<protocol>` in front
+        // of a packet is skipped instead of being glued to it.
+        let regex_str: &str = r#"<((room[\s\S]+?</room>)|errorpacket[\s\S]+?</errorpacket>|[^<>]*?/>)"#;
         let regex: Regex = Regex::new(regex_str).unwrap();
 
         match regex.find(&self.buffer) {
@@ -27,3 +29,9 @@ impl XmlBuffer {
         }
     }
 }
+
+impl Default for XmlBuffer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
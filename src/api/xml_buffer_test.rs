@@ -26,4 +26,13 @@ mod tests {
         let xml_bytes: Vec<u8> = buffer.pop_xml().unwrap();
         assert_eq!(xml_bytes, b"<room><name>Another Room</name></room>");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_skips_protocol_tag() {
+        let mut buffer: XmlBuffer = XmlBuffer::new();
+        buffer.push(br#"<protocol><errorpacket message="m"><originalRequest class="join"/></errorpacket><protocol><left roomId="r"/>"#);
+        assert_eq!(buffer.pop_xml().unwrap(), br#"<errorpacket message="m"><originalRequest class="join"/></errorpacket>"#);
+        assert_eq!(buffer.pop_xml().unwrap(), br#"<left roomId="r"/>"#);
+        assert_eq!(buffer.pop_xml(), None);
+    }
+}
@@ -0,0 +1,114 @@
+use crate::api::protocol::{Coordinate, Field, Ship, State, Team};
+use crate::api::rules::Undo;
+
+/// Feature tags, so that equal values of different features get different keys.
+const POSITION: u64 = 1;
+const DIRECTION: u64 = 2;
+const SPEED: u64 = 3;
+const COAL: u64 = 4;
+const PASSENGERS: u64 = 5;
+const FREE_TURNS: u64 = 6;
+const EMPTY_DOCK: u64 = 7;
+const CURRENT_TEAM: u64 = 8;
+
+/// 64-bit Zobrist hash of a [`State`].
+///
+/// Covers position, direction, speed, coal, passengers and free turns of every ship,
+/// the passenger docks that have been emptied and the current team. Board coordinates
+/// are unbounded, so keys are derived with a 64-bit mixing function instead of a
+/// precomputed table; a key is its own inverse under XOR like in a table-based scheme.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct ZobristHash(pub u64);
+
+impl ZobristHash {
+    pub fn of(state: &State) -> Self {
+        let mut hash = ZobristHash(team_key(&state.current_team));
+        for ship in &state.ship {
+            hash.0 ^= ship_key(ship);
+        }
+        for (coordinate, field) in state.board.fields() {
+            if let Field::Passenger(passenger) = field {
+                if passenger.passenger == 0 {
+                    hash.0 ^= dock_key(&coordinate);
+                }
+            }
+        }
+        hash
+    }
+
+    /// Replaces the contribution of a ship, call with the ship before and after a move.
+    pub fn update_ship(&mut self, before: &Ship, after: &Ship) {
+        self.0 ^= ship_key(before) ^ ship_key(after);
+    }
+
+    /// Toggles the emptied state of the passenger dock at `coordinate`.
+    pub fn toggle_dock(&mut self, coordinate: &Coordinate) {
+        self.0 ^= dock_key(coordinate);
+    }
+
+    pub fn update_current_team(&mut self, before: &Team, after: &Team) {
+        self.0 ^= team_key(before) ^ team_key(after);
+    }
+
+    /// Applies the changes of a move to the hash, given the state right after the move and
+    /// its undo record. Updating again with the same arguments takes the move back, so call
+    /// it after [`State::make_move`] and again before [`State::unmake_move`].
+    pub fn update(&mut self, state: &State, undo: &Undo) {
+        self.update_ship(&undo.before, &state.ship[undo.ship]);
+        self.update_current_team(&undo.current_team, &state.current_team);
+        if let Some((segment, column, row)) = undo.dock {
+            if let Some(Field::Passenger(passenger)) = state.board.segment[segment].field_array[column].fields.get(row) {
+                if passenger.passenger == 0 {
+                    self.toggle_dock(&state.board.segment[segment].coordinate_of(column, row));
+                }
+            }
+        }
+    }
+
+    /// Compares an incrementally updated hash against a full recompute. Only active in debug builds.
+    pub fn debug_verify(&self, state: &State) {
+        debug_assert_eq!(*self, ZobristHash::of(state), "incremental Zobrist hash diverged from state");
+    }
+}
+
+impl State {
+    pub fn zobrist_hash(&self) -> u64 {
+        ZobristHash::of(self).0
+    }
+}
+
+fn ship_key(ship: &Ship) -> u64 {
+    let team = match ship.team {
+        Team::One => 0,
+        Team::Two => 1,
+    };
+    key(POSITION, team, coordinate_value(&ship.position))
+        ^ key(DIRECTION, team, ship.direction.index() as u64)
+        ^ key(SPEED, team, ship.speed as u32 as u64)
+        ^ key(COAL, team, ship.coal as u32 as u64)
+        ^ key(PASSENGERS, team, ship.passengers as u32 as u64)
+        ^ key(FREE_TURNS, team, ship.free_turns as u32 as u64)
+}
+
+fn dock_key(coordinate: &Coordinate) -> u64 {
+    key(EMPTY_DOCK, 0, coordinate_value(coordinate))
+}
+
+fn team_key(team: &Team) -> u64 {
+    match team {
+        Team::One => 0,
+        Team::Two => key(CURRENT_TEAM, 0, 0),
+    }
+}
+
+fn coordinate_value(coordinate: &Coordinate) -> u64 {
+    ((coordinate.q as u16 as u64) << 16) | coordinate.r as u16 as u64
+}
+
+/// SplitMix64 finalizer over the packed feature.
+fn key(feature: u64, team: u64, value: u64) -> u64 {
+    let mut z = (feature << 56 | team << 48 | value).wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
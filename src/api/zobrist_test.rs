@@ -0,0 +1,86 @@
+#[cfg(test)]
+mod tests {
+    use crate::api::protocol::{Coordinate, CubeDirection, Field, Passenger, State, Team};
+    use crate::api::zobrist::ZobristHash;
+
+    fn state() -> State {
+        State::from_notation("1 1 4 R R@0,0:wwwww|wwwww|pUL1wwww|wwwww 1R@-1,-1:1,6,0,1,0 2R@-2,1:1,6,0,1,0").unwrap()
+    }
+
+    #[test]
+    fn test_equal_states_hash_equal() {
+        let mut other = state();
+        other.ship.reverse();
+        other.turn = 9;
+        assert_eq!(state().zobrist_hash(), other.zobrist_hash());
+    }
+
+    #[test]
+    fn test_features_change_hash() {
+        let base = state().zobrist_hash();
+        let changes: Vec<fn(&mut State)> = vec![
+            |s| s.ship[0].position = Coordinate::new(0, -1),
+            |s| s.ship[0].direction = CubeDirection::UpRight,
+            |s| s.ship[1].speed = 2,
+            |s| s.ship[1].coal = 5,
+            |s| s.ship[0].passengers = 1,
+            |s| s.ship[0].free_turns = 0,
+            |s| s.current_team = Team::Two,
+            |s| s.board.segment[0].field_array[2].fields[0] = Field::Passenger(Passenger { direction: CubeDirection::UpLeft, passenger: 0 }),
+        ];
+        for change in changes {
+            let mut changed = state();
+            change(&mut changed);
+            assert_ne!(changed.zobrist_hash(), base);
+        }
+
+        let mut swapped = state();
+        let (one, two) = (swapped.ship[0].position.clone(), swapped.ship[1].position.clone());
+        swapped.ship[0].position = two;
+        swapped.ship[1].position = one;
+        assert_ne!(swapped.zobrist_hash(), base);
+    }
+
+    #[test]
+    fn test_incremental_update() {
+        let mut state = state();
+        let mut hash = ZobristHash::of(&state);
+
+        // Every change is checked on its own against a full rehash, then undone.
+        let before = state.ship[0].clone();
+        state.ship[0].position = Coordinate::new(1, -1);
+        state.ship[0].direction = CubeDirection::DownRight;
+        state.ship[0].speed = 2;
+        state.ship[0].coal = 5;
+        state.ship[0].passengers = 1;
+        state.ship[0].free_turns = 0;
+        hash.update_ship(&before, &state.ship[0]);
+        assert_eq!(hash, ZobristHash::of(&state));
+        let after = state.ship[0].clone();
+        state.ship[0] = before.clone();
+        hash.update_ship(&after, &before);
+        assert_eq!(hash, ZobristHash::of(&state));
+
+        let before = state.ship[1].clone();
+        state.ship[1].coal = 3;
+        hash.update_ship(&before, &state.ship[1]);
+        assert_eq!(hash, ZobristHash::of(&state));
+
+        state.current_team = Team::Two;
+        hash.update_current_team(&Team::One, &Team::Two);
+        assert_eq!(hash, ZobristHash::of(&state));
+        state.current_team = Team::One;
+        hash.update_current_team(&Team::Two, &Team::One);
+        assert_eq!(hash, ZobristHash::of(&state));
+
+        let dock = state.board.segment[0].coordinate_of(2, 0);
+        state.board.segment[0].field_array[2].fields[0] = Field::Passenger(Passenger { direction: CubeDirection::UpLeft, passenger: 0 });
+        hash.toggle_dock(&dock);
+        assert_eq!(hash, ZobristHash::of(&state));
+        state.board.segment[0].field_array[2].fields[0] = Field::Passenger(Passenger { direction: CubeDirection::UpLeft, passenger: 1 });
+        hash.toggle_dock(&dock);
+        assert_eq!(hash, ZobristHash::of(&state));
+
+        hash.debug_verify(&state);
+    }
+}
@@ -0,0 +1,55 @@
+//! Runs the local game server.
+//!
+//! ```text
+//! server [--port 13050] [--seed 0] [--move-timeout-ms 2000] [--prepare ROOMS]
+//! ```
+//!
+//! `--prepare` opens rooms for `joinPrepared` and prints their reservation codes.
+
+use std::process;
+use std::str::FromStr;
+use std::time::Duration;
+
+use socha_rust_core::api::server::{Server, ServerConfig};
+
+const USAGE: &str = "Usage: server [--port 13050] [--seed 0] [--move-timeout-ms 2000] [--prepare ROOMS]";
+
+fn main() {
+    let mut config = ServerConfig::default();
+    let mut prepare = 0;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--port" => config.port = value(&arg, args.next()),
+            "--seed" => config.seed = value(&arg, args.next()),
+            "--move-timeout-ms" => config.move_timeout = Duration::from_millis(value(&arg, args.next())),
+            "--prepare" => prepare = value(&arg, args.next()),
+            "--help" | "-h" => {
+                println!("{}", USAGE);
+                return;
+            }
+            _ => exit(&format!("Unknown argument {}", arg)),
+        }
+    }
+
+    let server = Server::bind(config).unwrap_or_else(|error| exit(&format!("Can not listen: {}", error)));
+    println!("Listening on port {}", server.port());
+    for _ in 0..prepare {
+        let (room_id, [one, two]) = server.prepare_room();
+        println!("Room {}: reservation codes {} (team one) and {} (team two)", room_id, one, two);
+    }
+    if let Err(error) = server.run() {
+        exit(&format!("Server stopped: {}", error));
+    }
+}
+
+fn value<T: FromStr>(name: &str, value: Option<String>) -> T {
+    value
+        .and_then(|value| value.parse().ok())
+        .unwrap_or_else(|| exit(&format!("{} needs a number", name)))
+}
+
+fn exit(message: &str) -> ! {
+    eprintln!("{}\n{}", message, USAGE);
+    process::exit(2)
+}
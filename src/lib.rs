@@ -1 +1,7 @@
+// The yaserde derive macros expand to impl blocks inside anonymous consts.
+#![allow(non_local_definitions)]
+
 pub mod api;
+pub mod random;
+pub mod random_test;
+pub mod search;
@@ -0,0 +1,26 @@
+#[cfg(test)]
+mod tests {
+    use crate::random::Random;
+
+    #[test]
+    fn test_same_seed_same_sequence() {
+        let mut a = Random::new(17);
+        let mut b = Random::new(17);
+        for _ in 0..100 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+        assert_ne!(Random::new(1).next_u64(), Random::new(2).next_u64());
+    }
+
+    #[test]
+    fn test_ranges() {
+        let mut random = Random::new(3);
+        let mut seen = [false; 6];
+        for _ in 0..1000 {
+            seen[random.below(6)] = true;
+            let value = random.next_f64();
+            assert!((0.0..1.0).contains(&value));
+        }
+        assert!(seen.iter().all(|&s| s));
+    }
+}
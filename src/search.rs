@@ -0,0 +1,23 @@
+pub mod alpha_beta;
+pub mod alpha_beta_test;
+pub mod client;
+pub mod client_test;
+pub mod evaluation;
+pub mod evaluation_test;
+pub mod game;
+pub mod lazy_smp;
+pub mod lazy_smp_test;
+pub mod mcts;
+pub mod mcts_test;
+pub mod ponder;
+pub mod ponder_test;
+pub mod position;
+pub mod position_test;
+#[cfg(test)]
+pub mod test_game;
+pub mod time_manager;
+pub mod time_manager_test;
+pub mod tournament;
+pub mod tournament_test;
+pub mod transposition;
+pub mod transposition_test;
@@ -0,0 +1,244 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::search::game::Game;
+use crate::search::transposition::{move_key, Bound, TranspositionTable, TtEntry};
+
+/// Larger than any score `Game::evaluate` may return.
+pub const INFINITY: i32 = 1_000_000;
+
+/// Number of nodes between two checks of the clock.
+const TIME_CHECK_INTERVAL: u64 = 256;
+
+#[derive(Clone, Debug)]
+pub struct SearchConfig {
+    pub max_depth: u32,
+    pub time_limit: Duration,
+}
+
+impl Default for SearchConfig {
+    fn default() -> Self {
+        SearchConfig { max_depth: 64, time_limit: Duration::from_millis(1500) }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SearchResult<M> {
+    pub best_move: Option<M>,
+    pub score: i32,
+    /// Depth of the last completed iteration.
+    pub depth: u32,
+    pub principal_variation: Vec<M>,
+    pub nodes: u64,
+}
+
+/// Negamax search with alpha-beta pruning and iterative deepening.
+///
+/// Moves are ordered by the principal variation of the previous iteration, then the move from
+/// the transposition table, then two killer moves per ply, then the history heuristic. The
+/// search stops at `max_depth` or when the time limit is reached; an unfinished iteration is
+/// discarded. Setting the optional stop flag ends the search just like the time limit.
+pub struct AlphaBeta<G: Game> {
+    pub config: SearchConfig,
+    /// Optional transposition table, keyed by `Game::hash`.
+    pub table: Option<Arc<TranspositionTable>>,
+    pub stop: Option<Arc<AtomicBool>>,
+    /// Depth of the first iteration. Lazy SMP helpers start deeper than the main thread.
+    pub(crate) first_depth: u32,
+    killers: Vec<[Option<G::Move>; 2]>,
+    history: HashMap<G::Move, i32>,
+    previous_pv: Vec<G::Move>,
+    deadline: Instant,
+    nodes: u64,
+    stopped: bool,
+}
+
+impl<G: Game> AlphaBeta<G> {
+    pub fn new(config: SearchConfig) -> Self {
+        AlphaBeta {
+            config,
+            table: None,
+            stop: None,
+            first_depth: 1,
+            killers: Vec::new(),
+            history: HashMap::new(),
+            previous_pv: Vec::new(),
+            deadline: Instant::now(),
+            nodes: 0,
+            stopped: false,
+        }
+    }
+
+    /// Uses `table` for this and later searches. The table may be shared with other searches,
+    /// so the owner calls `TranspositionTable::new_search` before each one.
+    pub fn with_table(mut self, table: Arc<TranspositionTable>) -> Self {
+        self.table = Some(table);
+        self
+    }
+
+    pub fn with_stop(mut self, stop: Arc<AtomicBool>) -> Self {
+        self.stop = Some(stop);
+        self
+    }
+
+    pub fn search(&mut self, game: &G) -> SearchResult<G::Move> {
+        self.deadline = Instant::now() + self.config.time_limit;
+        self.killers.clear();
+        self.history.clear();
+        self.previous_pv.clear();
+        self.nodes = 0;
+        self.stopped = false;
+
+        let mut game = game.clone();
+        let mut result = SearchResult { best_move: None, score: 0, depth: 0, principal_variation: Vec::new(), nodes: 0 };
+        if let Some(first) = game.legal_moves().into_iter().next() {
+            result.best_move = Some(first.clone());
+            result.principal_variation = vec![first];
+        }
+
+        for depth in self.first_depth..=self.config.max_depth {
+            let mut pv = Vec::new();
+            let score = self.negamax(&mut game, depth, 0, -INFINITY, INFINITY, &mut pv);
+            if self.stopped {
+                break;
+            }
+
+            result.score = score;
+            result.depth = depth;
+            result.best_move = pv.first().cloned().or(result.best_move);
+            result.principal_variation = pv.clone();
+            self.previous_pv = pv;
+
+            if game.is_terminal() {
+                break;
+            }
+        }
+
+        result.nodes = self.nodes;
+        result
+    }
+
+    fn negamax(&mut self, game: &mut G, depth: u32, ply: usize, mut alpha: i32, beta: i32, pv: &mut Vec<G::Move>) -> i32 {
+        self.nodes += 1;
+        if self.nodes.is_multiple_of(TIME_CHECK_INTERVAL) && Instant::now() >= self.deadline {
+            self.stopped = true;
+        }
+        if self.stop.as_ref().is_some_and(|stop| stop.load(Ordering::Relaxed)) {
+            self.stopped = true;
+        }
+        if self.stopped {
+            return 0;
+        }
+
+        pv.clear();
+        if depth == 0 || game.is_terminal() {
+            return game.evaluate();
+        }
+
+        let hash = game.hash();
+        let entry = self.table.as_ref().and_then(|table| table.probe(hash));
+        if let Some(entry) = entry.filter(|entry| ply > 0 && entry.depth as u32 >= depth) {
+            let usable = match entry.bound {
+                Bound::Exact => true,
+                Bound::Lower => entry.score >= beta,
+                Bound::Upper => entry.score <= alpha,
+            };
+            if usable {
+                return entry.score;
+            }
+        }
+
+        let moves = self.ordered_moves(game, ply, entry.and_then(|entry| entry.best_move));
+        if moves.is_empty() {
+            return game.evaluate();
+        }
+
+        let player = game.current_player();
+        let original_alpha = alpha;
+        let mut best = -INFINITY;
+        let mut best_move = None;
+        let mut child_pv = Vec::new();
+        for m in moves {
+            let undo = game.make_move(&m);
+            let score = if game.current_player() == player {
+                self.negamax(game, depth - 1, ply + 1, alpha, beta, &mut child_pv)
+            } else {
+                -self.negamax(game, depth - 1, ply + 1, -beta, -alpha, &mut child_pv)
+            };
+            game.unmake_move(&m, undo);
+            if self.stopped {
+                return 0;
+            }
+
+            if score > best {
+                best = score;
+                best_move = Some(move_key(&m));
+                if score > alpha {
+                    alpha = score;
+                    pv.clear();
+                    pv.push(m.clone());
+                    pv.append(&mut child_pv);
+                }
+            }
+            if alpha >= beta {
+                self.store_killer(ply, &m);
+                let history = self.history.entry(m).or_insert(0);
+                *history = history.saturating_add((depth * depth) as i32);
+                break;
+            }
+        }
+
+        if let Some(table) = &self.table {
+            let bound = if best <= original_alpha {
+                Bound::Upper
+            } else if best >= beta {
+                Bound::Lower
+            } else {
+                Bound::Exact
+            };
+            let depth = depth.min(u8::MAX as u32) as u8;
+            table.store(hash, TtEntry { depth, bound, score: best, best_move });
+        }
+        best
+    }
+
+    /// Legal moves in search order. `table_move` is the [`move_key`] of the table's best move.
+    fn ordered_moves(&self, game: &G, ply: usize, table_move: Option<u16>) -> Vec<G::Move> {
+        let pv_move = self.previous_pv.get(ply);
+        let killers = self.killers.get(ply);
+        let mut scored: Vec<(i32, G::Move)> = game
+            .legal_moves()
+            .into_iter()
+            .map(|m| {
+                let score = if Some(&m) == pv_move {
+                    i32::MAX
+                } else if table_move.is_some() && table_move == Some(move_key(&m)) {
+                    i32::MAX - 1
+                } else if killers.is_some_and(|k| k[0].as_ref() == Some(&m)) {
+                    i32::MAX - 2
+                } else if killers.is_some_and(|k| k[1].as_ref() == Some(&m)) {
+                    i32::MAX - 3
+                } else {
+                    self.history.get(&m).copied().unwrap_or(0)
+                };
+                (score, m)
+            })
+            .collect();
+        scored.sort_by_key(|(score, _)| Reverse(*score));
+        scored.into_iter().map(|(_, m)| m).collect()
+    }
+
+    fn store_killer(&mut self, ply: usize, m: &G::Move) {
+        if self.killers.len() <= ply {
+            self.killers.resize(ply + 1, [None, None]);
+        }
+        let killers = &mut self.killers[ply];
+        if killers[0].as_ref() != Some(m) {
+            killers[1] = killers[0].take();
+            killers[0] = Some(m.clone());
+        }
+    }
+}
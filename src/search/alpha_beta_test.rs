@@ -0,0 +1,59 @@
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::search::alpha_beta::{AlphaBeta, SearchConfig};
+    use crate::search::game::Game;
+    use crate::search::test_game::{DoubleMove, TicTacToe, DOUBLE_MOVE_PAYOFF, WIN};
+
+    fn search(game: &TicTacToe) -> crate::search::alpha_beta::SearchResult<usize> {
+        AlphaBeta::new(SearchConfig { max_depth: 9, time_limit: Duration::from_secs(10) }).search(game)
+    }
+
+    #[test]
+    fn test_empty_board_is_a_draw() {
+        let result = search(&TicTacToe::new());
+        assert_eq!(result.score, 0);
+        assert_eq!(result.depth, 9);
+        assert!(result.best_move.is_some());
+    }
+
+    #[test]
+    fn test_finds_win() {
+        let result = search(&TicTacToe::parse("xx.oo...."));
+        assert_eq!(result.best_move, Some(2));
+        assert_eq!(result.score, WIN);
+    }
+
+    #[test]
+    fn test_blocks_threat() {
+        let result = search(&TicTacToe::parse("oo..x...."));
+        assert_eq!(result.best_move, Some(2));
+    }
+
+    #[test]
+    fn test_principal_variation_is_legal() {
+        let mut game = TicTacToe::parse("x...o....");
+        let result = search(&game);
+        assert_eq!(result.principal_variation.first(), result.best_move.as_ref());
+        for m in &result.principal_variation {
+            assert!(game.legal_moves().contains(m));
+            game.make_move(m);
+        }
+    }
+
+    #[test]
+    fn test_time_limit() {
+        let config = SearchConfig { max_depth: 64, time_limit: Duration::ZERO };
+        let result = AlphaBeta::new(config).search(&TicTacToe::new());
+        assert!(result.best_move.is_some());
+        assert!(result.depth < 9);
+    }
+
+    #[test]
+    fn test_same_player_moves_twice() {
+        let result = AlphaBeta::new(SearchConfig { max_depth: 2, time_limit: Duration::from_secs(10) }).search(&DoubleMove::default());
+        assert_eq!(result.principal_variation, vec![0, 1]);
+        assert_eq!(result.score, DOUBLE_MOVE_PAYOFF[0][1]);
+    }
+}
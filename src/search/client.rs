@@ -0,0 +1,216 @@
+use std::time::Duration;
+
+use crate::api::game_client::{ClientInterface, GameContext};
+use crate::api::protocol::{ErrorPacket, Joined, Left, Move, Room, State};
+use crate::search::alpha_beta::{AlphaBeta, SearchConfig};
+use crate::search::game::Game;
+use crate::search::lazy_smp::{LazySmp, ParallelConfig};
+use crate::search::mcts::{Mcts, MctsConfig, PlayoutPolicy};
+use crate::search::ponder::Ponderer;
+
+/// Ready-made [`ClientInterface`] that answers every move request with an [`AlphaBeta`] search
+/// on the last received state. The configured time limit is shortened to the budget of the
+/// context's time manager, like in the other search clients.
+pub struct SearchClient<G: Game> {
+    pub search: AlphaBeta<G>,
+    pub state: Option<State>,
+}
+
+impl<G> SearchClient<G>
+where
+    G: Game + From<State>,
+    G::Move: Into<Move>,
+{
+    pub fn new(config: SearchConfig) -> Self {
+        SearchClient { search: AlphaBeta::new(config), state: None }
+    }
+}
+
+impl<G> ClientInterface for SearchClient<G>
+where
+    G: Game + From<State>,
+    G::Move: Into<Move>,
+{
+    fn on_state(&mut self, state: Room, _context: &GameContext) {
+        if let Some(state) = state.data.state {
+            self.state = Some(state);
+        }
+    }
+
+    fn on_move_request(&mut self, context: &GameContext) -> Move {
+        let Some(state) = self.state.clone() else {
+            return Move::default();
+        };
+        let turn = state.turn;
+        let game = G::from(state);
+        let configured = self.search.config.time_limit;
+        self.search.config.time_limit = time_limit(configured, context, turn, &game);
+        let result = self.search.search(&game);
+        self.search.config.time_limit = configured;
+        println!(
+            "Search: depth {}, score {}, {} nodes, pv {:?}",
+            result.depth, result.score, result.nodes, result.principal_variation
+        );
+        result.best_move.map(Into::into).unwrap_or_default()
+    }
+
+    fn on_error_packet(&mut self, _error_packet: ErrorPacket) {}
+
+    fn on_erroneous_move(&mut self, _error: Room, _context: &GameContext) {}
+
+    fn on_joined(&mut self, _joined: Joined) {}
+
+    fn on_welcome(&mut self, _welcome_message: Room, _context: &GameContext) {}
+
+    fn on_left(&mut self, _left: Left) {}
+
+    fn on_result(&mut self, _result: Room, _context: &GameContext) {}
+}
+
+/// Like [`SearchClient`], but searches with [`LazySmp`] on several threads.
+///
+/// If the `GameClient` has pondering enabled, the position after the own move is searched on
+/// the opponent's turn with the same transposition table.
+pub struct ParallelSearchClient<G: Game> {
+    pub search: LazySmp<G>,
+    pub ponderer: Ponderer<G>,
+    pub state: Option<State>,
+    ponder_position: Option<G>,
+}
+
+impl<G> ParallelSearchClient<G>
+where
+    G: Game + Send + Sync + From<State> + 'static,
+    G::Move: Send + Into<Move> + 'static,
+{
+    pub fn new(config: ParallelConfig) -> Self {
+        let search = LazySmp::new(config);
+        let ponderer = Ponderer::new(search.table.clone());
+        ParallelSearchClient { search, ponderer, state: None, ponder_position: None }
+    }
+}
+
+impl<G> ClientInterface for ParallelSearchClient<G>
+where
+    G: Game + Send + Sync + From<State> + 'static,
+    G::Move: Send + Into<Move> + 'static,
+{
+    fn on_state(&mut self, state: Room, _context: &GameContext) {
+        if let Some(state) = state.data.state {
+            self.state = Some(state);
+        }
+    }
+
+    fn on_move_request(&mut self, context: &GameContext) -> Move {
+        let Some(state) = self.state.clone() else {
+            return Move::default();
+        };
+        let turn = state.turn;
+        let game = G::from(state);
+        let configured = self.search.config.search.time_limit;
+        self.search.config.search.time_limit = time_limit(configured, context, turn, &game);
+        let result = self.search.search(&game);
+        self.search.config.search.time_limit = configured;
+        println!(
+            "Search on {} threads: depth {}, score {}, {} nodes, pv {:?}",
+            self.search.config.threads, result.depth, result.score, result.nodes, result.principal_variation
+        );
+        let Some(best_move) = result.best_move else {
+            return Move::default();
+        };
+        let mut next = game;
+        next.make_move(&best_move);
+        self.ponder_position = Some(next);
+        best_move.into()
+    }
+
+    fn on_ponder_start(&mut self) {
+        if let Some(position) = self.ponder_position.take() {
+            self.ponderer.start(position);
+        }
+    }
+
+    fn on_ponder_stop(&mut self) {
+        if let Some(result) = self.ponderer.stop() {
+            println!("Ponder: depth {}, {} nodes, expected reply {:?}", result.depth, result.nodes, result.best_move);
+        }
+    }
+
+    fn on_error_packet(&mut self, _error_packet: ErrorPacket) {}
+
+    fn on_erroneous_move(&mut self, _error: Room, _context: &GameContext) {}
+
+    fn on_joined(&mut self, _joined: Joined) {}
+
+    fn on_welcome(&mut self, _welcome_message: Room, _context: &GameContext) {}
+
+    fn on_left(&mut self, _left: Left) {}
+
+    fn on_result(&mut self, _result: Room, _context: &GameContext) {}
+}
+
+/// Ready-made [`ClientInterface`] that answers every move request with an [`Mcts`] search.
+/// The tree is kept between turns and re-rooted at the position of each new memento.
+pub struct MctsClient<G: Game, P: PlayoutPolicy<G>> {
+    pub mcts: Mcts<G, P>,
+    pub state: Option<State>,
+}
+
+impl<G, P> MctsClient<G, P>
+where
+    G: Game + PartialEq + From<State>,
+    G::Move: Into<Move>,
+    P: PlayoutPolicy<G>,
+{
+    pub fn new(config: MctsConfig, policy: P) -> Self {
+        MctsClient { mcts: Mcts::new(config, policy), state: None }
+    }
+}
+
+impl<G, P> ClientInterface for MctsClient<G, P>
+where
+    G: Game + PartialEq + From<State>,
+    G::Move: Into<Move>,
+    P: PlayoutPolicy<G>,
+{
+    fn on_state(&mut self, state: Room, _context: &GameContext) {
+        if let Some(state) = state.data.state {
+            self.state = Some(state);
+        }
+    }
+
+    fn on_move_request(&mut self, context: &GameContext) -> Move {
+        let Some(state) = self.state.clone() else {
+            return Move::default();
+        };
+        let turn = state.turn;
+        let game = G::from(state);
+        let configured = self.mcts.config.time_limit;
+        self.mcts.config.time_limit = time_limit(configured, context, turn, &game);
+        let best_move = self.mcts.search(&game);
+        self.mcts.config.time_limit = configured;
+        let Some(best_move) = best_move else {
+            return Move::default();
+        };
+        println!("MCTS: {} iterations in the tree, playing {:?}", self.mcts.root_visits(), best_move);
+        self.mcts.advance(&best_move);
+        best_move.into()
+    }
+
+    fn on_error_packet(&mut self, _error_packet: ErrorPacket) {}
+
+    fn on_erroneous_move(&mut self, _error: Room, _context: &GameContext) {}
+
+    fn on_joined(&mut self, _joined: Joined) {}
+
+    fn on_welcome(&mut self, _welcome_message: Room, _context: &GameContext) {}
+
+    fn on_left(&mut self, _left: Left) {}
+
+    fn on_result(&mut self, _result: Room, _context: &GameContext) {}
+}
+
+/// The configured limit, shortened to what the time manager grants for this position.
+fn time_limit<G: Game>(configured: Duration, context: &GameContext, turn: i32, game: &G) -> Duration {
+    configured.min(context.time_manager.budget(turn.max(0) as u32, game.legal_moves().len()))
+}
@@ -0,0 +1,111 @@
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::api::game_client::{ClientInterface, GameContext};
+    use crate::api::protocol::{Coordinate, Data, DataClass, Move, Room, State};
+    use crate::search::alpha_beta::SearchConfig;
+    use crate::search::client::{MctsClient, ParallelSearchClient, SearchClient};
+    use crate::search::game::Game;
+    use crate::search::lazy_smp::ParallelConfig;
+    use crate::search::mcts::{MctsConfig, RandomPlayout};
+
+    /// Take one or two from a pile, whoever takes the last one wins. The pile is the turn of the state.
+    #[derive(Clone, PartialEq)]
+    struct Pile {
+        stones: i32,
+        player: u8,
+    }
+
+    impl From<State> for Pile {
+        fn from(state: State) -> Self {
+            Pile { stones: state.turn, player: 1 }
+        }
+    }
+
+    #[derive(Clone, Debug, PartialEq, Eq, Hash)]
+    struct Take(i32);
+
+    impl From<Take> for Move {
+        fn from(take: Take) -> Self {
+            Move { from: Coordinate::new(take.0, 0), to: Coordinate::new(0, 0) }
+        }
+    }
+
+    impl Game for Pile {
+        type Move = Take;
+        type Undo = ();
+
+        fn legal_moves(&self) -> Vec<Take> {
+            (1..=self.stones.min(2)).map(Take).collect()
+        }
+
+        fn make_move(&mut self, m: &Take) {
+            self.stones -= m.0;
+            self.player = 3 - self.player;
+        }
+
+        fn unmake_move(&mut self, m: &Take, _undo: ()) {
+            self.stones += m.0;
+            self.player = 3 - self.player;
+        }
+
+        fn is_terminal(&self) -> bool {
+            self.stones == 0
+        }
+
+        fn current_player(&self) -> u8 {
+            self.player
+        }
+
+        fn evaluate(&self) -> i32 {
+            if self.stones == 0 { -100 } else { 0 }
+        }
+
+        fn hash(&self) -> u64 {
+            (self.stones as u64) << 1 | self.player as u64
+        }
+    }
+
+    fn memento(pile: i32) -> Room {
+        let state = State { turn: pile, ..Default::default() };
+        let data = Data { class: DataClass::Memento, color: None, state: Some(state), _move: None, definition: None, scores: None, winner: None };
+        Room { room_id: "room".to_string(), data }
+    }
+
+    #[test]
+    fn test_search_client_answers_move_request() {
+        let mut client: SearchClient<Pile> = SearchClient::new(SearchConfig { max_depth: 10, time_limit: Duration::from_secs(5) });
+        assert_eq!(client.on_move_request(&GameContext::default()), Move::default());
+
+        client.on_state(memento(7), &GameContext::default());
+        assert_eq!(client.on_move_request(&GameContext::default()).from, Coordinate::new(1, 0));
+    }
+
+    #[test]
+    fn test_parallel_search_client_answers_move_request() {
+        let search = SearchConfig { max_depth: 10, time_limit: Duration::from_secs(5) };
+        let mut client: ParallelSearchClient<Pile> = ParallelSearchClient::new(ParallelConfig { search, threads: 3, table_megabytes: 1 });
+        client.on_state(memento(7), &GameContext::default());
+        assert_eq!(client.on_move_request(&GameContext::default()).from, Coordinate::new(1, 0));
+
+        client.on_ponder_start();
+        assert!(client.ponderer.is_running());
+        client.on_ponder_stop();
+        assert!(!client.ponderer.is_running());
+    }
+
+    #[test]
+    fn test_mcts_client_answers_move_request() {
+        let config = MctsConfig { iterations: Some(2000), seed: 5, ..Default::default() };
+        let mut client: MctsClient<Pile, RandomPlayout> = MctsClient::new(config, RandomPlayout);
+
+        client.on_state(memento(7), &GameContext::default());
+        assert_eq!(client.on_move_request(&GameContext::default()).from, Coordinate::new(1, 0));
+
+        // The opponent took two, the tree below that reply is reused.
+        client.on_state(memento(4), &GameContext::default());
+        assert_eq!(client.on_move_request(&GameContext::default()).from, Coordinate::new(1, 0));
+        assert!(client.mcts.root_visits() > 0);
+    }
+}
@@ -0,0 +1,150 @@
+use crate::api::board::SEGMENT_COLUMNS;
+use crate::api::protocol::{Field, Ship, State, Team};
+
+/// Static evaluation of a state from the view of `team`. Larger is better for `team`.
+///
+/// The search uses an evaluator through [`Position`](crate::search::position::Position).
+pub trait Evaluator {
+    fn evaluate(&self, state: &State, team: Team) -> i32;
+}
+
+impl<F: Fn(&State, Team) -> i32> Evaluator for F {
+    fn evaluate(&self, state: &State, team: Team) -> i32 {
+        self(state, team)
+    }
+}
+
+/// Weighted sum of other evaluators.
+#[derive(Default)]
+pub struct LinearEvaluator {
+    pub terms: Vec<(i32, Box<dyn Evaluator + Send + Sync>)>,
+}
+
+impl LinearEvaluator {
+    pub fn new() -> Self {
+        LinearEvaluator { terms: Vec::new() }
+    }
+
+    pub fn with(mut self, weight: i32, evaluator: impl Evaluator + Send + Sync + 'static) -> Self {
+        self.terms.push((weight, Box::new(evaluator)));
+        self
+    }
+
+    /// The unweighted value of every term, e.g. as features for weight tuning.
+    pub fn features(&self, state: &State, team: Team) -> Vec<i32> {
+        self.terms.iter().map(|(_, evaluator)| evaluator.evaluate(state, team.clone())).collect()
+    }
+}
+
+impl Evaluator for LinearEvaluator {
+    fn evaluate(&self, state: &State, team: Team) -> i32 {
+        self.terms.iter().map(|(weight, evaluator)| weight * evaluator.evaluate(state, team.clone())).sum()
+    }
+}
+
+/// Number of segments the ship is ahead of the opponent.
+pub struct SegmentProgress;
+
+/// Number of field columns the ship is ahead of the opponent, counted over all segments.
+pub struct FieldProgress;
+
+/// Passengers on board compared to the opponent.
+pub struct Passengers;
+
+/// Coal left compared to the opponent.
+pub struct Coal;
+
+/// How close the speed is to `target`, compared to the opponent. Each step away costs one point.
+pub struct SpeedFitness {
+    pub target: i32,
+}
+
+/// Hex distance between the two ships. Use a negative weight to stay close to the opponent.
+pub struct DistanceToOpponent;
+
+/// Passenger docks with waiting passengers whose landing field is within `speed + 1` fields,
+/// compared to the opponent. This ignores obstacles and is only an estimate of what the ship
+/// can reach in its next move.
+pub struct ReachablePassengers;
+
+impl Evaluator for SegmentProgress {
+    fn evaluate(&self, state: &State, team: Team) -> i32 {
+        difference(state, team, |ship| state.board.segment_index(&ship.position).map_or(0, |index| index as i32))
+    }
+}
+
+impl Evaluator for FieldProgress {
+    fn evaluate(&self, state: &State, team: Team) -> i32 {
+        difference(state, team, |ship| field_progress(state, ship))
+    }
+}
+
+impl Evaluator for Passengers {
+    fn evaluate(&self, state: &State, team: Team) -> i32 {
+        difference(state, team, |ship| ship.passengers)
+    }
+}
+
+impl Evaluator for Coal {
+    fn evaluate(&self, state: &State, team: Team) -> i32 {
+        difference(state, team, |ship| ship.coal)
+    }
+}
+
+impl Evaluator for SpeedFitness {
+    fn evaluate(&self, state: &State, team: Team) -> i32 {
+        difference(state, team, |ship| -(ship.speed - self.target).abs())
+    }
+}
+
+impl Evaluator for DistanceToOpponent {
+    fn evaluate(&self, state: &State, team: Team) -> i32 {
+        match (ship_of(state, &team), ship_of(state, &team.opponent())) {
+            (Some(own), Some(other)) => own.position.distance_to(&other.position),
+            _ => 0,
+        }
+    }
+}
+
+impl Evaluator for ReachablePassengers {
+    fn evaluate(&self, state: &State, team: Team) -> i32 {
+        let landing_fields: Vec<_> = state
+            .board
+            .fields()
+            .filter_map(|(coordinate, field)| match field {
+                Field::Passenger(passenger) if passenger.passenger > 0 => {
+                    passenger.direction.vector().map(|vector| coordinate + vector)
+                }
+                _ => None,
+            })
+            .collect();
+        difference(state, team, |ship| {
+            let reach = ship.speed.max(1) + 1;
+            landing_fields.iter().filter(|field| field.distance_to(&ship.position) <= reach).count() as i32
+        })
+    }
+}
+
+fn ship_of<'a>(state: &'a State, team: &Team) -> Option<&'a Ship> {
+    state.ship.iter().find(|ship| ship.team == *team)
+}
+
+/// `feature(own ship) - feature(opponent ship)`, with a missing ship counting as zero.
+fn difference(state: &State, team: Team, feature: impl Fn(&Ship) -> i32) -> i32 {
+    let own = ship_of(state, &team).map_or(0, &feature);
+    let other = ship_of(state, &team.opponent()).map_or(0, &feature);
+    own - other
+}
+
+fn field_progress(state: &State, ship: &Ship) -> i32 {
+    state
+        .board
+        .segment
+        .iter()
+        .enumerate()
+        .find_map(|(index, segment)| {
+            let (column, _) = segment.index_of(&ship.position)?;
+            Some((index * SEGMENT_COLUMNS + column) as i32)
+        })
+        .unwrap_or(0)
+}
@@ -0,0 +1,48 @@
+#[cfg(test)]
+mod tests {
+    use crate::api::protocol::{State, Team};
+    use crate::search::evaluation::{
+        Coal, DistanceToOpponent, Evaluator, FieldProgress, LinearEvaluator, Passengers, ReachablePassengers, SegmentProgress,
+        SpeedFitness,
+    };
+
+    /// Team ONE in the second segment next to a dock, team TWO at the start.
+    const NOTATION: &str = "1 1 6 R R@0,0:wwwww|wwwww|wwwww|wwwww/R@4,0:wwwww|wpUR1www|wwwww|wwwww 1R@4,0:2,4,1,1,0 2R@-1,0:4,6,0,1,0";
+
+    fn state() -> State {
+        State::from_notation(NOTATION).unwrap()
+    }
+
+    #[test]
+    fn test_progress() {
+        assert_eq!(SegmentProgress.evaluate(&state(), Team::One), 1);
+        assert_eq!(SegmentProgress.evaluate(&state(), Team::Two), -1);
+        assert_eq!(FieldProgress.evaluate(&state(), Team::One), 5);
+    }
+
+    #[test]
+    fn test_ship_features() {
+        assert_eq!(Passengers.evaluate(&state(), Team::One), 1);
+        assert_eq!(Coal.evaluate(&state(), Team::One), -2);
+        assert_eq!(SpeedFitness { target: 2 }.evaluate(&state(), Team::One), 2);
+        assert_eq!(DistanceToOpponent.evaluate(&state(), Team::Two), 5);
+    }
+
+    #[test]
+    fn test_reachable_passengers() {
+        assert_eq!(ReachablePassengers.evaluate(&state(), Team::One), 1);
+        let mut far = state();
+        far.ship[0].position = far.ship[1].position.clone();
+        assert_eq!(ReachablePassengers.evaluate(&far, Team::One), 0);
+    }
+
+    #[test]
+    fn test_linear_combination() {
+        let evaluator = LinearEvaluator::new()
+            .with(10, SegmentProgress)
+            .with(3, Passengers)
+            .with(-1, |_: &State, _: Team| 7);
+        assert_eq!(evaluator.evaluate(&state(), Team::One), 10 + 3 - 7);
+        assert_eq!(evaluator.features(&state(), Team::Two), vec![-1, -1, 7]);
+    }
+}
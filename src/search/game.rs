@@ -0,0 +1,32 @@
+use std::fmt::Debug;
+use std::hash::Hash;
+
+/// A two-player, zero-sum game position the search algorithms can work on.
+///
+/// A bot implements this trait for a type that can be built from a protocol `State`, on top
+/// of the simplified rules in `api::rules` or of its own rules code.
+pub trait Game: Clone {
+    type Move: Clone + Debug + Eq + Hash;
+
+    /// Whatever `unmake_move` needs to restore the position.
+    type Undo;
+
+    fn legal_moves(&self) -> Vec<Self::Move>;
+
+    fn make_move(&mut self, m: &Self::Move) -> Self::Undo;
+
+    fn unmake_move(&mut self, m: &Self::Move, undo: Self::Undo);
+
+    fn is_terminal(&self) -> bool;
+
+    /// The player to move. The search negates a score only when this changes across a move,
+    /// because in Mississippi Queen a team can move twice in a row.
+    fn current_player(&self) -> u8;
+
+    /// Static score of the position from the view of the player to move. Larger is better.
+    fn evaluate(&self) -> i32;
+
+    /// Hash of the position including the player to move, used as transposition table key.
+    /// For a `State` this is `State::zobrist_hash`.
+    fn hash(&self) -> u64;
+}
@@ -0,0 +1,98 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+
+use crate::search::alpha_beta::{AlphaBeta, SearchConfig, SearchResult};
+use crate::search::game::Game;
+use crate::search::transposition::TranspositionTable;
+
+#[derive(Clone, Debug)]
+pub struct ParallelConfig {
+    pub search: SearchConfig,
+    /// Number of search threads including the calling one. Defaults to the number of cores.
+    pub threads: usize,
+    /// Size of the shared transposition table.
+    pub table_megabytes: usize,
+}
+
+impl Default for ParallelConfig {
+    fn default() -> Self {
+        ParallelConfig {
+            search: SearchConfig::default(),
+            threads: thread::available_parallelism().map_or(1, |threads| threads.get()),
+            table_megabytes: 64,
+        }
+    }
+}
+
+/// Parallel [`AlphaBeta`] search with the Lazy SMP scheme.
+///
+/// All threads search the same position and only share the transposition table, which is kept
+/// between searches. Helper threads with an odd index skip the first iteration so the threads
+/// spread over different depths. The main thread runs on the caller's thread; when it stops,
+/// at the time limit or at `max_depth`, the helpers are stopped too. The move of the deepest
+/// completed iteration is played, preferring the main thread on equal depth.
+///
+/// With a single thread nothing is spawned and the search is as deterministic as [`AlphaBeta`]
+/// with a transposition table.
+pub struct LazySmp<G: Game> {
+    pub config: ParallelConfig,
+    pub table: Arc<TranspositionTable>,
+    stop: Arc<AtomicBool>,
+    main: AlphaBeta<G>,
+}
+
+impl<G> LazySmp<G>
+where
+    G: Game + Send + Sync,
+    G::Move: Send,
+{
+    pub fn new(config: ParallelConfig) -> Self {
+        let table = Arc::new(TranspositionTable::new(config.table_megabytes));
+        let stop = Arc::new(AtomicBool::new(false));
+        let main = AlphaBeta::new(config.search.clone()).with_table(table.clone()).with_stop(stop.clone());
+        LazySmp { config, table, stop, main }
+    }
+
+    /// Flag that stops the running search when set, e.g. from another thread.
+    /// It is reset at the start of every search.
+    pub fn stop_flag(&self) -> Arc<AtomicBool> {
+        self.stop.clone()
+    }
+
+    pub fn search(&mut self, game: &G) -> SearchResult<G::Move> {
+        self.stop.store(false, Ordering::Relaxed);
+        self.table.new_search();
+        self.main.config = self.config.search.clone();
+        if self.config.threads <= 1 {
+            return self.main.search(game);
+        }
+
+        let main = &mut self.main;
+        let stop = &self.stop;
+        let (mut result, helpers) = thread::scope(|scope| {
+            let handles: Vec<_> = (1..self.config.threads)
+                .map(|index| {
+                    let mut helper = AlphaBeta::new(self.config.search.clone())
+                        .with_table(self.table.clone())
+                        .with_stop(stop.clone());
+                    helper.first_depth = 1 + (index % 2) as u32;
+                    scope.spawn(move || helper.search(game))
+                })
+                .collect();
+            let result = main.search(game);
+            stop.store(true, Ordering::Relaxed);
+            let helpers: Vec<_> = handles.into_iter().map(|handle| handle.join().expect("search thread panicked")).collect();
+            (result, helpers)
+        });
+
+        let nodes = result.nodes + helpers.iter().map(|helper| helper.nodes).sum::<u64>();
+        for helper in helpers {
+            if helper.depth > result.depth && helper.best_move.is_some() {
+                result = helper;
+            }
+        }
+        result.nodes = nodes;
+        result
+    }
+}
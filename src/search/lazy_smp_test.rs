@@ -0,0 +1,56 @@
+#[cfg(test)]
+mod tests {
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    use crate::search::alpha_beta::SearchConfig;
+    use crate::search::lazy_smp::{LazySmp, ParallelConfig};
+    use crate::search::test_game::{TicTacToe, WIN};
+
+    fn config(threads: usize, max_depth: u32, time_limit: Duration) -> ParallelConfig {
+        ParallelConfig { search: SearchConfig { max_depth, time_limit }, threads, table_megabytes: 1 }
+    }
+
+    #[test]
+    fn test_single_thread_is_deterministic() {
+        let search = || LazySmp::new(config(1, 9, Duration::from_secs(10))).search(&TicTacToe::parse("x...o...."));
+        let first = search();
+        assert_eq!(first, search());
+        assert_eq!(first.depth, 9);
+    }
+
+    #[test]
+    fn test_threads_agree_on_score() {
+        let mut search = LazySmp::new(config(4, 9, Duration::from_secs(10)));
+        let result = search.search(&TicTacToe::new());
+        assert_eq!(result.score, 0);
+        assert_eq!(result.depth, 9);
+
+        let result = search.search(&TicTacToe::parse("xx.oo...."));
+        assert_eq!(result.best_move, Some(2));
+        assert_eq!(result.score, WIN);
+    }
+
+    #[test]
+    fn test_stops_at_deadline() {
+        let start = Instant::now();
+        let result = LazySmp::new(config(4, 64, Duration::from_millis(50))).search(&TicTacToe::new());
+        assert!(start.elapsed() < Duration::from_millis(500));
+        assert!(result.best_move.is_some());
+    }
+
+    #[test]
+    fn test_stop_flag() {
+        let mut search = LazySmp::new(config(2, 64, Duration::from_secs(60)));
+        let stop = search.stop_flag();
+        let stopper = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(50));
+            stop.store(true, std::sync::atomic::Ordering::Relaxed);
+        });
+        let start = Instant::now();
+        let result = search.search(&TicTacToe::new());
+        stopper.join().unwrap();
+        assert!(start.elapsed() < Duration::from_secs(5));
+        assert!(result.best_move.is_some());
+    }
+}
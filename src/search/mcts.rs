@@ -0,0 +1,270 @@
+use std::time::{Duration, Instant};
+
+use crate::random::Random;
+use crate::search::game::Game;
+
+/// Chooses moves during the simulation phase of [`Mcts`].
+pub trait PlayoutPolicy<G: Game> {
+    /// Returns the index of the move to play out of `moves`, which is never empty.
+    fn choose(&mut self, game: &G, moves: &[G::Move], random: &mut Random) -> usize;
+}
+
+/// Plays uniformly random moves.
+#[derive(Clone, Debug, Default)]
+pub struct RandomPlayout;
+
+impl<G: Game> PlayoutPolicy<G> for RandomPlayout {
+    fn choose(&mut self, _game: &G, moves: &[G::Move], random: &mut Random) -> usize {
+        random.below(moves.len())
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct MctsConfig {
+    /// UCT exploration constant `c` in `value + c * sqrt(ln(parent visits) / visits)`.
+    pub exploration: f64,
+    /// Iterations per search. If set, the time limit is ignored so that results only depend on the seed.
+    pub iterations: Option<u32>,
+    pub time_limit: Duration,
+    /// Playouts stop after this many moves and score the position with `Game::evaluate`.
+    pub max_playout_depth: u32,
+    /// Evaluation that maps to a win probability of about 73%, see [`Mcts`].
+    pub evaluation_scale: f64,
+    pub seed: u64,
+}
+
+impl Default for MctsConfig {
+    fn default() -> Self {
+        MctsConfig {
+            exploration: std::f64::consts::SQRT_2,
+            iterations: None,
+            time_limit: Duration::from_millis(1500),
+            max_playout_depth: 40,
+            evaluation_scale: 100.0,
+            seed: 0,
+        }
+    }
+}
+
+struct Node<M> {
+    /// Move that leads from the parent to this node.
+    mv: Option<M>,
+    children: Vec<usize>,
+    untried: Vec<M>,
+    /// Player who played `mv`. The root stores the player to move, its reward is never read.
+    mover: u8,
+    visits: u32,
+    /// Sum of rewards from the view of the player who played `mv`.
+    reward: f64,
+}
+
+/// Monte Carlo tree search with UCT selection.
+///
+/// Playout results are turned into rewards in `[0, 1]` with a logistic function of
+/// `Game::evaluate`, so terminal positions should evaluate far outside `evaluation_scale`.
+/// With an iteration limit the search is fully deterministic for a given seed.
+pub struct Mcts<G: Game, P: PlayoutPolicy<G>> {
+    pub config: MctsConfig,
+    pub policy: P,
+    random: Random,
+    root: Option<G>,
+    nodes: Vec<Node<G::Move>>,
+}
+
+impl<G: Game, P: PlayoutPolicy<G>> Mcts<G, P> {
+    pub fn new(config: MctsConfig, policy: P) -> Self {
+        let random = Random::new(config.seed);
+        Mcts { config, policy, random, root: None, nodes: Vec::new() }
+    }
+
+    /// Runs the search from `game`, reusing the current tree if its root is the same position.
+    pub fn search(&mut self, game: &G) -> Option<G::Move>
+    where
+        G: PartialEq,
+    {
+        if !self.reroot(game) {
+            self.reset(game.clone());
+        }
+        self.run();
+        self.best_move()
+    }
+
+    /// Number of completed iterations stored in the current root.
+    pub fn root_visits(&self) -> u32 {
+        self.nodes.first().map_or(0, |node| node.visits)
+    }
+
+    /// Moves the root to the child reached by `m`, keeping its subtree. Starts a new tree if
+    /// the move was never expanded.
+    pub fn advance(&mut self, m: &G::Move) {
+        let Some(mut game) = self.root.take() else {
+            return;
+        };
+        let child = self.nodes[0].children.iter().copied().find(|&child| self.nodes[child].mv.as_ref() == Some(m));
+        game.make_move(m);
+        match child {
+            Some(child) => self.keep_subtree(child, game),
+            None => self.reset(game),
+        }
+    }
+
+    /// Looks for `game` among the root and the positions up to two moves below it, and makes
+    /// that node the new root. Returns whether the tree could be reused.
+    pub fn reroot(&mut self, game: &G) -> bool
+    where
+        G: PartialEq,
+    {
+        let Some(root) = self.root.clone() else {
+            return false;
+        };
+        if root == *game {
+            return true;
+        }
+        let mut frontier = vec![(0, root)];
+        for _ in 0..2 {
+            let mut next = Vec::new();
+            for (node, position) in frontier {
+                for &child in &self.nodes[node].children {
+                    let mut child_position = position.clone();
+                    if let Some(m) = &self.nodes[child].mv {
+                        child_position.make_move(m);
+                    }
+                    if child_position == *game {
+                        self.keep_subtree(child, child_position);
+                        return true;
+                    }
+                    next.push((child, child_position));
+                }
+            }
+            frontier = next;
+        }
+        false
+    }
+
+    fn reset(&mut self, game: G) {
+        let mover = game.current_player();
+        self.nodes = vec![Node { mv: None, children: Vec::new(), untried: expandable_moves(&game), mover, visits: 0, reward: 0.0 }];
+        self.root = Some(game);
+    }
+
+    /// Copies the subtree below `node` into a fresh arena with `node` as the root.
+    fn keep_subtree(&mut self, node: usize, game: G) {
+        let mut old: Vec<Option<Node<G::Move>>> = std::mem::take(&mut self.nodes).into_iter().map(Some).collect();
+        let mut queue = vec![(node, None::<usize>)];
+        while let Some((index, parent)) = queue.pop() {
+            let mut copied = old[index].take().expect("tree nodes have a single parent");
+            let new_index = self.nodes.len();
+            let children = std::mem::take(&mut copied.children);
+            if let Some(parent) = parent {
+                self.nodes[parent].children.push(new_index);
+            }
+            self.nodes.push(copied);
+            queue.extend(children.into_iter().rev().map(|child| (child, Some(new_index))));
+        }
+        self.nodes[0].mv = None;
+        self.root = Some(game);
+    }
+
+    fn run(&mut self) {
+        let Some(root) = self.root.clone() else {
+            return;
+        };
+        let deadline = Instant::now() + self.config.time_limit;
+        let mut iteration = 0;
+        loop {
+            let done = match self.config.iterations {
+                Some(limit) => iteration >= limit,
+                None => iteration > 0 && Instant::now() >= deadline,
+            };
+            if done {
+                break;
+            }
+            self.iterate(&mut root.clone());
+            iteration += 1;
+        }
+    }
+
+    fn iterate(&mut self, game: &mut G) {
+        let mut path = vec![0];
+        let mut node = 0;
+
+        // Selection
+        while self.nodes[node].untried.is_empty() && !self.nodes[node].children.is_empty() {
+            node = self.select_child(node);
+            if let Some(m) = &self.nodes[node].mv {
+                game.make_move(m);
+            }
+            path.push(node);
+        }
+
+        // Expansion
+        if !self.nodes[node].untried.is_empty() {
+            let index = self.random.below(self.nodes[node].untried.len());
+            let m = self.nodes[node].untried.swap_remove(index);
+            let mover = game.current_player();
+            game.make_move(&m);
+            let child = self.nodes.len();
+            self.nodes.push(Node { mv: Some(m), children: Vec::new(), untried: expandable_moves(game), mover, visits: 0, reward: 0.0 });
+            self.nodes[node].children.push(child);
+            path.push(child);
+        }
+
+        // Simulation: the reward is from the view of the player to move after the playout.
+        let mut depth = 0;
+        while depth < self.config.max_playout_depth && !game.is_terminal() {
+            let moves = game.legal_moves();
+            if moves.is_empty() {
+                break;
+            }
+            let index = self.policy.choose(game, &moves, &mut self.random);
+            game.make_move(&moves[index]);
+            depth += 1;
+        }
+        let reward = 1.0 / (1.0 + (-(game.evaluate() as f64) / self.config.evaluation_scale).exp());
+        let player = game.current_player();
+
+        // Backpropagation: each node is credited from the view of the player who moved into it,
+        // which need not alternate along the path.
+        for &index in path.iter().rev() {
+            let node = &mut self.nodes[index];
+            node.visits += 1;
+            node.reward += if node.mover == player { reward } else { 1.0 - reward };
+        }
+    }
+
+    fn select_child(&self, node: usize) -> usize {
+        let parent_visits = (self.nodes[node].visits.max(1) as f64).ln();
+        let uct = |child: usize| {
+            let child = &self.nodes[child];
+            let visits = child.visits.max(1) as f64;
+            child.reward / visits + self.config.exploration * (parent_visits / visits).sqrt()
+        };
+        let mut best = self.nodes[node].children[0];
+        let mut best_value = uct(best);
+        for &child in &self.nodes[node].children[1..] {
+            let value = uct(child);
+            if value > best_value {
+                best = child;
+                best_value = value;
+            }
+        }
+        best
+    }
+
+    fn best_move(&self) -> Option<G::Move> {
+        let root = self.nodes.first()?;
+        let best = root.children.iter().copied().max_by_key(|&child| self.nodes[child].visits);
+        match best {
+            Some(child) => self.nodes[child].mv.clone(),
+            None => root.untried.first().cloned(),
+        }
+    }
+}
+
+fn expandable_moves<G: Game>(game: &G) -> Vec<G::Move> {
+    if game.is_terminal() {
+        Vec::new()
+    } else {
+        game.legal_moves()
+    }
+}
@@ -0,0 +1,67 @@
+#[cfg(test)]
+mod tests {
+    use crate::search::game::Game;
+    use crate::search::mcts::{Mcts, MctsConfig, RandomPlayout};
+    use crate::search::test_game::{DoubleMove, TicTacToe};
+
+    fn mcts(seed: u64, iterations: u32) -> Mcts<TicTacToe, RandomPlayout> {
+        let config = MctsConfig { iterations: Some(iterations), evaluation_scale: 100.0, seed, ..Default::default() };
+        Mcts::new(config, RandomPlayout)
+    }
+
+    #[test]
+    fn test_finds_win() {
+        let mut mcts = mcts(1, 2000);
+        assert_eq!(mcts.search(&TicTacToe::parse("xx.oo....")), Some(2));
+    }
+
+    #[test]
+    fn test_blocks_threat() {
+        let mut mcts = mcts(2, 5000);
+        assert_eq!(mcts.search(&TicTacToe::parse("oo..x....")), Some(2));
+    }
+
+    #[test]
+    fn test_deterministic_under_seed() {
+        let game = TicTacToe::parse("x...o....");
+        let first = mcts(42, 500).search(&game);
+        for _ in 0..3 {
+            assert_eq!(mcts(42, 500).search(&game), first);
+        }
+    }
+
+    #[test]
+    fn test_tree_reuse() {
+        let mut mcts = mcts(7, 1000);
+        let mut game = TicTacToe::new();
+        let first = mcts.search(&game).unwrap();
+        mcts.advance(&first);
+        game.make_move(&first);
+
+        let reply = game.legal_moves()[0];
+        game.make_move(&reply);
+        let visits_before = mcts.root_visits();
+        assert!(mcts.reroot(&game));
+        assert!(mcts.root_visits() > 0);
+        assert!(mcts.root_visits() < visits_before);
+
+        mcts.search(&game);
+        assert!(mcts.root_visits() > 1000);
+    }
+
+    #[test]
+    fn test_unknown_position_starts_new_tree() {
+        let mut mcts = mcts(7, 100);
+        mcts.search(&TicTacToe::new());
+        assert!(!mcts.reroot(&TicTacToe::parse("xo.xo.x..")));
+        mcts.search(&TicTacToe::parse("x.o.x.o.."));
+        assert_eq!(mcts.root_visits(), 100);
+    }
+
+    #[test]
+    fn test_same_player_moves_twice() {
+        let config = MctsConfig { iterations: Some(500), evaluation_scale: 10.0, seed: 3, ..Default::default() };
+        let mut mcts = Mcts::new(config, RandomPlayout);
+        assert_eq!(mcts.search(&DoubleMove::default()), Some(0));
+    }
+}
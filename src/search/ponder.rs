@@ -0,0 +1,69 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use crate::search::alpha_beta::{AlphaBeta, SearchConfig, SearchResult};
+use crate::search::game::Game;
+use crate::search::transposition::TranspositionTable;
+
+/// Long enough to only stop on request, short enough not to overflow `Instant`.
+const NO_TIME_LIMIT: Duration = Duration::from_secs(365 * 24 * 60 * 60);
+
+/// Searches the position after the own move in a background thread while the opponent thinks.
+///
+/// The search has no time limit and runs until [`Ponderer::stop`]. It writes into the given
+/// transposition table, so a later search of any reply of the opponent finds the results for
+/// the positions it already looked at.
+pub struct Ponderer<G: Game> {
+    pub table: Arc<TranspositionTable>,
+    pub max_depth: u32,
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<SearchResult<G::Move>>>,
+}
+
+impl<G> Ponderer<G>
+where
+    G: Game + Send + 'static,
+    G::Move: Send + 'static,
+{
+    pub fn new(table: Arc<TranspositionTable>) -> Self {
+        Ponderer { table, max_depth: 64, stop: Arc::new(AtomicBool::new(false)), handle: None }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.handle.is_some()
+    }
+
+    /// Whether the background search ended by itself after reaching `max_depth`.
+    pub fn is_finished(&self) -> bool {
+        self.handle.as_ref().is_some_and(|handle| handle.is_finished())
+    }
+
+    /// Starts pondering on `game`, the position with the opponent to move. A search that is
+    /// still running is stopped first.
+    pub fn start(&mut self, game: G) {
+        self.stop();
+        self.stop = Arc::new(AtomicBool::new(false));
+        let config = SearchConfig { max_depth: self.max_depth, time_limit: NO_TIME_LIMIT };
+        let mut search = AlphaBeta::new(config).with_table(self.table.clone()).with_stop(self.stop.clone());
+        self.handle = Some(thread::spawn(move || search.search(&game)));
+    }
+
+    /// Stops the background search and returns its result, with the expected reply of the
+    /// opponent as best move. Returns `None` if nothing was running.
+    pub fn stop(&mut self) -> Option<SearchResult<G::Move>> {
+        let handle = self.handle.take()?;
+        self.stop.store(true, Ordering::Relaxed);
+        Some(handle.join().expect("ponder thread panicked"))
+    }
+}
+
+impl<G: Game> Drop for Ponderer<G> {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
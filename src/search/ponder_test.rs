@@ -0,0 +1,54 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    use crate::search::alpha_beta::{AlphaBeta, SearchConfig};
+    use crate::search::ponder::Ponderer;
+    use crate::search::test_game::TicTacToe;
+    use crate::search::transposition::TranspositionTable;
+
+    #[test]
+    fn test_stop_without_start() {
+        let mut ponderer: Ponderer<TicTacToe> = Ponderer::new(Arc::new(TranspositionTable::new(1)));
+        assert!(!ponderer.is_running());
+        assert_eq!(ponderer.stop(), None);
+    }
+
+    #[test]
+    fn test_ponder_is_interrupted() {
+        let mut ponderer = Ponderer::new(Arc::new(TranspositionTable::new(1)));
+        ponderer.max_depth = u32::MAX;
+        ponderer.start(TicTacToe::parse("x........"));
+        assert!(ponderer.is_running());
+        thread::sleep(Duration::from_millis(20));
+
+        let start = Instant::now();
+        let result = ponderer.stop().unwrap();
+        assert!(start.elapsed() < Duration::from_secs(1));
+        assert!(!ponderer.is_running());
+        assert!(result.best_move.is_some());
+    }
+
+    #[test]
+    fn test_ponder_results_are_reused() {
+        let table = Arc::new(TranspositionTable::new(1));
+        let config = SearchConfig { max_depth: 9, time_limit: Duration::from_secs(10) };
+        // Our move was the centre, the opponent answers in a corner.
+        let reply = TicTacToe::parse("o...x....");
+        let cold = AlphaBeta::new(config.clone()).with_table(Arc::new(TranspositionTable::new(1))).search(&reply);
+
+        let mut ponderer = Ponderer::new(table.clone());
+        ponderer.max_depth = 9;
+        ponderer.start(TicTacToe::parse("....x...."));
+        while !ponderer.is_finished() {
+            thread::sleep(Duration::from_millis(1));
+        }
+        assert_eq!(ponderer.stop().unwrap().depth, 9);
+
+        let warm = AlphaBeta::new(config).with_table(table).search(&reply);
+        assert_eq!(warm.score, cold.score);
+        assert!(warm.nodes < cold.nodes);
+    }
+}
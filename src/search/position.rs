@@ -0,0 +1,80 @@
+use std::sync::Arc;
+
+use crate::api::protocol::{Move, State, Team};
+use crate::api::rules::Undo;
+use crate::api::zobrist::ZobristHash;
+use crate::search::evaluation::Evaluator;
+use crate::search::game::Game;
+
+/// A protocol [`State`] as a [`Game`]: moves come from the rules engine in `api::rules` and
+/// positions are scored by an [`Evaluator`], so search and weight tuning share one evaluation.
+///
+/// The Zobrist hash is updated incrementally and checked against a full recompute in debug builds.
+/// `From<State>` uses `E::default()`, which lets the search clients build positions themselves.
+pub struct Position<E: Evaluator> {
+    pub state: State,
+    pub evaluator: Arc<E>,
+    hash: ZobristHash,
+}
+
+impl<E: Evaluator> Position<E> {
+    pub fn new(state: State, evaluator: Arc<E>) -> Self {
+        let hash = ZobristHash::of(&state);
+        Position { state, evaluator, hash }
+    }
+}
+
+impl<E: Evaluator> Clone for Position<E> {
+    fn clone(&self) -> Self {
+        Position { state: self.state.clone(), evaluator: Arc::clone(&self.evaluator), hash: self.hash }
+    }
+}
+
+impl<E: Evaluator + Default> From<State> for Position<E> {
+    fn from(state: State) -> Self {
+        Position::new(state, Arc::new(E::default()))
+    }
+}
+
+impl<E: Evaluator> Game for Position<E> {
+    type Move = Move;
+    type Undo = Undo;
+
+    fn legal_moves(&self) -> Vec<Move> {
+        if self.state.is_game_over() {
+            return Vec::new();
+        }
+        self.state.legal_moves()
+    }
+
+    fn make_move(&mut self, m: &Move) -> Undo {
+        let undo = self.state.make_move(m).expect("the search only plays legal moves");
+        self.hash.update(&self.state, &undo);
+        self.hash.debug_verify(&self.state);
+        undo
+    }
+
+    fn unmake_move(&mut self, _m: &Move, undo: Undo) {
+        self.hash.update(&self.state, &undo);
+        self.state.unmake_move(undo);
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.state.is_game_over()
+    }
+
+    fn current_player(&self) -> u8 {
+        match self.state.current_team {
+            Team::One => 1,
+            Team::Two => 2,
+        }
+    }
+
+    fn evaluate(&self) -> i32 {
+        self.evaluator.evaluate(&self.state, self.state.current_team.clone())
+    }
+
+    fn hash(&self) -> u64 {
+        self.hash.0
+    }
+}
@@ -0,0 +1,51 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use crate::api::protocol::{Coordinate, Move, State};
+    use crate::search::alpha_beta::{AlphaBeta, SearchConfig};
+    use crate::search::evaluation::{Coal, FieldProgress, LinearEvaluator};
+    use crate::search::game::Game;
+    use crate::search::position::Position;
+
+    fn position(evaluator: LinearEvaluator) -> Position<LinearEvaluator> {
+        let state = State::from_notation("1 1 0 R R@0,0:wwwww|wwwww|wwwww|wwwww 1R@-1,-1:1,6,0,1,0 2R@-2,1:1,6,0,1,0").unwrap();
+        Position::new(state, Arc::new(evaluator))
+    }
+
+    fn search(position: &Position<LinearEvaluator>) -> (Option<Move>, i32) {
+        let result = AlphaBeta::new(SearchConfig { max_depth: 1, time_limit: Duration::from_secs(10) }).search(position);
+        (result.best_move, result.score)
+    }
+
+    fn to(q: i32, r: i32) -> Move {
+        Move { from: Coordinate::new(-1, -1), to: Coordinate::new(q, r) }
+    }
+
+    #[test]
+    fn test_search_uses_evaluator() {
+        // Three columns ahead, paid with one coal for the acceleration to speed 3.
+        assert_eq!(search(&position(LinearEvaluator::new().with(1, FieldProgress))), (Some(to(2, -1)), 3));
+        // Valuing coal makes the free acceleration to speed 2 the better move.
+        let weighted = LinearEvaluator::new().with(1, FieldProgress).with(2, Coal);
+        assert_eq!(search(&position(weighted)), (Some(to(1, -1)), 2));
+    }
+
+    #[test]
+    fn test_make_and_unmake() {
+        let mut position = position(LinearEvaluator::new().with(1, FieldProgress));
+        let start = position.state.clone();
+        let hash = position.hash();
+        let m = to(2, -1);
+
+        let undo = position.make_move(&m);
+        assert_eq!(position.current_player(), 2);
+        assert_eq!(position.evaluate(), -3);
+        assert_eq!(position.hash(), position.state.zobrist_hash());
+
+        position.unmake_move(&m, undo);
+        assert_eq!(position.state, start);
+        assert_eq!(position.hash(), hash);
+    }
+}
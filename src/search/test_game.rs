@@ -0,0 +1,130 @@
+use crate::search::game::Game;
+
+/// Tic-tac-toe, small enough to be searched completely in tests.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TicTacToe {
+    pub cells: [u8; 9],
+    pub to_move: u8,
+}
+
+pub const WIN: i32 = 1000;
+
+const LINES: [[usize; 3]; 8] = [[0, 1, 2], [3, 4, 5], [6, 7, 8], [0, 3, 6], [1, 4, 7], [2, 5, 8], [0, 4, 8], [2, 4, 6]];
+
+impl TicTacToe {
+    pub fn new() -> Self {
+        TicTacToe { cells: [0; 9], to_move: 1 }
+    }
+
+    /// Builds a position from a 9 character string of `x`, `o` and `.`, with `x` to move if the counts are equal.
+    pub fn parse(cells: &str) -> Self {
+        let mut game = TicTacToe::new();
+        for (i, c) in cells.chars().enumerate() {
+            game.cells[i] = match c {
+                'x' => 1,
+                'o' => 2,
+                _ => 0,
+            };
+        }
+        let x = game.cells.iter().filter(|&&c| c == 1).count();
+        let o = game.cells.iter().filter(|&&c| c == 2).count();
+        game.to_move = if x > o { 2 } else { 1 };
+        game
+    }
+
+    pub fn winner(&self) -> Option<u8> {
+        LINES.iter().find_map(|line| {
+            let first = self.cells[line[0]];
+            (first != 0 && line.iter().all(|&i| self.cells[i] == first)).then_some(first)
+        })
+    }
+}
+
+impl Game for TicTacToe {
+    type Move = usize;
+    type Undo = ();
+
+    fn legal_moves(&self) -> Vec<usize> {
+        if self.winner().is_some() {
+            return Vec::new();
+        }
+        (0..9).filter(|&i| self.cells[i] == 0).collect()
+    }
+
+    fn make_move(&mut self, m: &usize) {
+        self.cells[*m] = self.to_move;
+        self.to_move = 3 - self.to_move;
+    }
+
+    fn unmake_move(&mut self, m: &usize, _undo: ()) {
+        self.cells[*m] = 0;
+        self.to_move = 3 - self.to_move;
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.winner().is_some() || self.cells.iter().all(|&c| c != 0)
+    }
+
+    fn current_player(&self) -> u8 {
+        self.to_move
+    }
+
+    fn evaluate(&self) -> i32 {
+        match self.winner() {
+            Some(player) if player == self.to_move => WIN,
+            Some(_) => -WIN,
+            None => 0,
+        }
+    }
+
+    fn hash(&self) -> u64 {
+        self.cells.iter().fold(self.to_move as u64, |hash, &cell| hash * 3 + cell as u64)
+    }
+}
+
+/// Player 1 moves twice in a row and then the game is over. `DOUBLE_MOVE_PAYOFF[first][second]`
+/// is the result for player 1, so the best line is `0, 1`, but a search that assumes the
+/// players alternate expects the second move to minimize and plays `1`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DoubleMove {
+    pub moves: Vec<usize>,
+}
+
+pub const DOUBLE_MOVE_PAYOFF: [[i32; 2]; 2] = [[-1, 5], [1, 1]];
+
+impl Game for DoubleMove {
+    type Move = usize;
+    type Undo = ();
+
+    fn legal_moves(&self) -> Vec<usize> {
+        if self.is_terminal() { Vec::new() } else { vec![0, 1] }
+    }
+
+    fn make_move(&mut self, m: &usize) {
+        self.moves.push(*m);
+    }
+
+    fn unmake_move(&mut self, _m: &usize, _undo: ()) {
+        self.moves.pop();
+    }
+
+    fn is_terminal(&self) -> bool {
+        self.moves.len() == 2
+    }
+
+    fn current_player(&self) -> u8 {
+        if self.is_terminal() { 2 } else { 1 }
+    }
+
+    /// From the view of player 2 once the game is over.
+    fn evaluate(&self) -> i32 {
+        match self.moves[..] {
+            [first, second] => -DOUBLE_MOVE_PAYOFF[first][second],
+            _ => 0,
+        }
+    }
+
+    fn hash(&self) -> u64 {
+        self.moves.iter().fold(1, |hash, &m| hash * 3 + m as u64)
+    }
+}
@@ -0,0 +1,88 @@
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Number of round trips the safety margin is based on.
+const ROUND_TRIP_SAMPLES: usize = 16;
+
+#[derive(Clone, Debug)]
+pub struct TimeConfig {
+    /// Time the server allows for one move.
+    pub move_time: Duration,
+    /// Number of turns in a full game.
+    pub max_turns: u32,
+    /// Share of the time used on the first turn. It grows linearly to the full time at half the game.
+    pub opening_share: f64,
+    /// Number of legal moves at which a position gets the full time. Positions with fewer moves
+    /// get proportionally less, but at least half.
+    pub typical_moves: usize,
+    /// Margin that is always kept, on top of the measured latency.
+    pub min_margin: Duration,
+}
+
+impl Default for TimeConfig {
+    fn default() -> Self {
+        TimeConfig {
+            move_time: Duration::from_millis(2000),
+            max_turns: 60,
+            opening_share: 0.6,
+            typical_moves: 20,
+            min_margin: Duration::from_millis(100),
+        }
+    }
+}
+
+/// Decides how long to think about a move.
+///
+/// The budget is the move time minus a safety margin, scaled by the game phase and by the
+/// number of legal moves. The margin is `min_margin` plus the slowest of the last round trips
+/// passed to [`TimeManager::record_round_trip`], so a slow connection leaves more time for the
+/// move to reach the server.
+#[derive(Clone, Debug)]
+pub struct TimeManager {
+    pub config: TimeConfig,
+    round_trips: VecDeque<Duration>,
+}
+
+impl TimeManager {
+    pub fn new(config: TimeConfig) -> Self {
+        TimeManager { config, round_trips: VecDeque::with_capacity(ROUND_TRIP_SAMPLES) }
+    }
+
+    /// Records the measured time between a request and the server's answer.
+    pub fn record_round_trip(&mut self, round_trip: Duration) {
+        if self.round_trips.len() == ROUND_TRIP_SAMPLES {
+            self.round_trips.pop_front();
+        }
+        self.round_trips.push_back(round_trip);
+    }
+
+    pub fn safety_margin(&self) -> Duration {
+        self.config.min_margin + self.round_trips.iter().max().copied().unwrap_or_default()
+    }
+
+    /// Time to think on `turn` in a position with `legal_moves` moves. A forced move gets no time.
+    pub fn budget(&self, turn: u32, legal_moves: usize) -> Duration {
+        if legal_moves <= 1 {
+            return Duration::ZERO;
+        }
+        let available = self.config.move_time.saturating_sub(self.safety_margin());
+        available.mul_f64(self.phase_factor(turn) * self.complexity_factor(legal_moves))
+    }
+
+    fn phase_factor(&self, turn: u32) -> f64 {
+        let half = (self.config.max_turns as f64 / 2.0).max(1.0);
+        let progress = (turn as f64 / half).min(1.0);
+        let opening = self.config.opening_share.clamp(0.0, 1.0);
+        opening + (1.0 - opening) * progress
+    }
+
+    fn complexity_factor(&self, legal_moves: usize) -> f64 {
+        (legal_moves as f64 / self.config.typical_moves.max(1) as f64).clamp(0.5, 1.0)
+    }
+}
+
+impl Default for TimeManager {
+    fn default() -> Self {
+        TimeManager::new(TimeConfig::default())
+    }
+}
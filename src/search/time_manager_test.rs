@@ -0,0 +1,54 @@
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::search::time_manager::{TimeConfig, TimeManager};
+
+    fn assert_millis(actual: Duration, millis: u64) {
+        let expected = Duration::from_millis(millis);
+        assert!(actual.abs_diff(expected) < Duration::from_millis(1), "expected {:?}, got {:?}", expected, actual);
+    }
+
+    #[test]
+    fn test_phase() {
+        let manager = TimeManager::default();
+        assert_millis(manager.budget(0, 20), 1140);
+        assert_millis(manager.budget(15, 20), 1520);
+        assert_millis(manager.budget(30, 20), 1900);
+        assert_millis(manager.budget(59, 20), 1900);
+    }
+
+    #[test]
+    fn test_complexity() {
+        let manager = TimeManager::default();
+        assert_eq!(manager.budget(30, 1), Duration::ZERO);
+        assert_eq!(manager.budget(30, 0), Duration::ZERO);
+        assert_millis(manager.budget(30, 2), 950);
+        assert_millis(manager.budget(30, 15), 1425);
+        assert_millis(manager.budget(30, 100), 1900);
+    }
+
+    #[test]
+    fn test_round_trip_margin() {
+        let mut manager = TimeManager::new(TimeConfig { min_margin: Duration::from_millis(50), ..Default::default() });
+        assert_eq!(manager.safety_margin(), Duration::from_millis(50));
+
+        manager.record_round_trip(Duration::from_millis(300));
+        manager.record_round_trip(Duration::from_millis(20));
+        assert_eq!(manager.safety_margin(), Duration::from_millis(350));
+        assert_millis(manager.budget(30, 20), 1650);
+
+        // The slow round trip is forgotten after enough new measurements.
+        for _ in 0..16 {
+            manager.record_round_trip(Duration::from_millis(20));
+        }
+        assert_eq!(manager.safety_margin(), Duration::from_millis(70));
+    }
+
+    #[test]
+    fn test_margin_larger_than_move_time() {
+        let mut manager = TimeManager::default();
+        manager.record_round_trip(Duration::from_secs(5));
+        assert_eq!(manager.budget(30, 20), Duration::ZERO);
+    }
+}
@@ -0,0 +1,388 @@
+use std::fmt::Write;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+use crate::random::Random;
+use crate::search::alpha_beta::AlphaBeta;
+use crate::search::game::Game;
+use crate::search::mcts::{Mcts, PlayoutPolicy};
+
+/// Lower bound for the variance of a game score in the SPRT.
+const MIN_VARIANCE: f64 = 0.05;
+
+/// Anything that picks moves, e.g. a search with a fixed configuration.
+pub trait Player<G: Game> {
+    fn play(&mut self, game: &G) -> Option<G::Move>;
+}
+
+impl<G: Game, F: FnMut(&G) -> Option<G::Move>> Player<G> for F {
+    fn play(&mut self, game: &G) -> Option<G::Move> {
+        self(game)
+    }
+}
+
+impl<G: Game> Player<G> for AlphaBeta<G> {
+    fn play(&mut self, game: &G) -> Option<G::Move> {
+        self.search(game).best_move
+    }
+}
+
+impl<G: Game + PartialEq, P: PlayoutPolicy<G>> Player<G> for Mcts<G, P> {
+    fn play(&mut self, game: &G) -> Option<G::Move> {
+        self.search(game)
+    }
+}
+
+/// Sequential probability ratio test of `elo0` against `elo1`, with error rates `alpha`
+/// (accepting H1 although H0 is true) and `beta`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct SprtConfig {
+    pub elo0: f64,
+    pub elo1: f64,
+    pub alpha: f64,
+    pub beta: f64,
+}
+
+impl Default for SprtConfig {
+    fn default() -> Self {
+        SprtConfig { elo0: 0.0, elo1: 10.0, alpha: 0.05, beta: 0.05 }
+    }
+}
+
+impl SprtConfig {
+    pub fn lower_bound(&self) -> f64 {
+        (self.beta / (1.0 - self.alpha)).ln()
+    }
+
+    pub fn upper_bound(&self) -> f64 {
+        ((1.0 - self.beta) / self.alpha).ln()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SprtDecision {
+    /// The candidate is not better by `elo1`.
+    AcceptH0,
+    /// The candidate is better by at least `elo0`.
+    AcceptH1,
+    Continue,
+}
+
+#[derive(Clone, Debug)]
+pub struct TournamentConfig {
+    /// Number of games, rounded up to an even number so every board is played with both colours.
+    pub games: usize,
+    pub threads: usize,
+    /// Seed for the per-board seeds passed to the game constructor.
+    pub seed: u64,
+    /// Games that reach this many moves are adjudicated by `Game::evaluate`.
+    pub max_plies: u32,
+    /// Stops early once the test reaches a decision. Game pairs that have started are still
+    /// finished, so both colours always get the same number of games.
+    pub sprt: Option<SprtConfig>,
+}
+
+impl Default for TournamentConfig {
+    fn default() -> Self {
+        TournamentConfig {
+            games: 100,
+            threads: thread::available_parallelism().map_or(1, |threads| threads.get()),
+            seed: 0,
+            max_plies: 200,
+            sprt: None,
+        }
+    }
+}
+
+/// Result of a game from the view of the candidate.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Outcome {
+    Win,
+    Draw,
+    Loss,
+}
+
+impl Outcome {
+    pub fn score(&self) -> f64 {
+        match self {
+            Outcome::Win => 1.0,
+            Outcome::Draw => 0.5,
+            Outcome::Loss => 0.0,
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Outcome::Win => "win",
+            Outcome::Draw => "draw",
+            Outcome::Loss => "loss",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct GameRecord {
+    #[cfg_attr(feature = "serde", serde(rename = "game"))]
+    pub index: usize,
+    /// Seed the game was created from. Both games of a pair share it.
+    pub seed: u64,
+    pub candidate_first: bool,
+    pub outcome: Outcome,
+    pub plies: u32,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct TournamentResult {
+    /// Finished games ordered by index. Fewer than configured if the SPRT stopped early.
+    pub games: Vec<GameRecord>,
+    pub sprt: Option<SprtConfig>,
+}
+
+/// Elo difference with the half width of its 95% confidence interval.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub struct Elo {
+    pub difference: f64,
+    pub error: f64,
+}
+
+impl TournamentResult {
+    pub fn wins(&self) -> usize {
+        self.count(Outcome::Win)
+    }
+
+    pub fn draws(&self) -> usize {
+        self.count(Outcome::Draw)
+    }
+
+    pub fn losses(&self) -> usize {
+        self.count(Outcome::Loss)
+    }
+
+    /// Average score of the candidate, between 0 and 1.
+    pub fn score(&self) -> f64 {
+        if self.games.is_empty() {
+            return 0.5;
+        }
+        self.games.iter().map(|game| game.outcome.score()).sum::<f64>() / self.games.len() as f64
+    }
+
+    /// Elo difference of the candidate over the baseline. Infinite if one side won every game.
+    pub fn elo(&self) -> Elo {
+        let n = self.games.len() as f64;
+        let score = self.score();
+        let deviation = (self.variance() / n.max(1.0)).sqrt();
+        let lower = elo_from_score(score - 1.96 * deviation);
+        let upper = elo_from_score(score + 1.96 * deviation);
+        Elo { difference: elo_from_score(score), error: (upper - lower) / 2.0 }
+    }
+
+    /// Log-likelihood ratio of the SPRT, using the normal approximation of the game scores.
+    /// The variance has a floor, so the test still decides when every game ends the same way.
+    pub fn llr(&self) -> Option<f64> {
+        let sprt = self.sprt.as_ref()?;
+        if self.games.is_empty() {
+            return Some(0.0);
+        }
+        let variance = self.variance().max(MIN_VARIANCE);
+        let n = self.games.len() as f64;
+        let (s0, s1) = (score_from_elo(sprt.elo0), score_from_elo(sprt.elo1));
+        Some(n * (s1 - s0) * (2.0 * self.score() - s0 - s1) / (2.0 * variance))
+    }
+
+    pub fn sprt_decision(&self) -> Option<SprtDecision> {
+        let sprt = self.sprt.as_ref()?;
+        let llr = self.llr()?;
+        Some(if llr <= sprt.lower_bound() {
+            SprtDecision::AcceptH0
+        } else if llr >= sprt.upper_bound() {
+            SprtDecision::AcceptH1
+        } else {
+            SprtDecision::Continue
+        })
+    }
+
+    /// One line per game: `game,seed,candidate_first,outcome,plies`.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("game,seed,candidate_first,outcome,plies\n");
+        for game in &self.games {
+            writeln!(csv, "{},{},{},{},{}", game.index, game.seed, game.candidate_first, game.outcome.name(), game.plies).unwrap();
+        }
+        csv
+    }
+
+    /// Summary and games as a JSON object with camelCase keys, like the serde types of the crate.
+    /// Non-finite numbers are written as `null`.
+    pub fn to_json(&self) -> String {
+        let elo = self.elo();
+        let mut json = format!(
+            "{{\"games\":{},\"wins\":{},\"draws\":{},\"losses\":{},\"score\":{},\"elo\":{},\"eloError\":{}",
+            self.games.len(),
+            self.wins(),
+            self.draws(),
+            self.losses(),
+            json_number(self.score()),
+            json_number(elo.difference),
+            json_number(elo.error)
+        );
+        if let (Some(llr), Some(decision)) = (self.llr(), self.sprt_decision()) {
+            let decision = match decision {
+                SprtDecision::AcceptH0 => "H0",
+                SprtDecision::AcceptH1 => "H1",
+                SprtDecision::Continue => "continue",
+            };
+            write!(json, ",\"llr\":{},\"sprt\":\"{}\"", json_number(llr), decision).unwrap();
+        }
+        json.push_str(",\"results\":[");
+        for (i, game) in self.games.iter().enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+            write!(
+                json,
+                "{{\"game\":{},\"seed\":{},\"candidateFirst\":{},\"outcome\":\"{}\",\"plies\":{}}}",
+                game.index,
+                game.seed,
+                game.candidate_first,
+                game.outcome.name(),
+                game.plies
+            )
+            .unwrap();
+        }
+        json.push_str("]}");
+        json
+    }
+
+    fn count(&self, outcome: Outcome) -> usize {
+        self.games.iter().filter(|game| game.outcome == outcome).count()
+    }
+
+    /// Variance of a single game score.
+    fn variance(&self) -> f64 {
+        if self.games.is_empty() {
+            return 0.0;
+        }
+        let score = self.score();
+        self.games.iter().map(|game| (game.outcome.score() - score).powi(2)).sum::<f64>() / self.games.len() as f64
+    }
+}
+
+/// Plays `config.games` games between the candidate and the baseline on several threads.
+///
+/// Game pairs share a seed that is passed to `new_game`, e.g. to generate a board, and are
+/// played once with each side moving first. Each thread plays whole pairs, so an SPRT stop
+/// never leaves a pair unfinished. Players are created by their factories for every game on the
+/// thread that plays it. A side may move several times in a row: each move is made by the player
+/// of the side to move, and the winner is decided by the sign of `Game::evaluate` in the final
+/// position, taken from the view of the side to move there.
+pub fn run_tournament<G, N, C, PC, B, PB>(config: &TournamentConfig, new_game: N, candidate: C, baseline: B) -> TournamentResult
+where
+    G: Game,
+    N: Fn(u64) -> G + Sync,
+    C: Fn() -> PC + Sync,
+    PC: Player<G>,
+    B: Fn() -> PB + Sync,
+    PB: Player<G>,
+{
+    let mut random = Random::new(config.seed);
+    let seeds: Vec<u64> = (0..config.games.div_ceil(2)).map(|_| random.next_u64()).collect();
+    let next = AtomicUsize::new(0);
+    let stop = AtomicBool::new(false);
+    let mut result = TournamentResult { games: Vec::new(), sprt: config.sprt.clone() };
+
+    thread::scope(|scope| {
+        let (sender, receiver) = mpsc::channel();
+        for _ in 0..config.threads.max(1) {
+            let sender = sender.clone();
+            let (next, stop, seeds, new_game, candidate, baseline) = (&next, &stop, &seeds, &new_game, &candidate, &baseline);
+            scope.spawn(move || loop {
+                let pair = next.fetch_add(1, Ordering::Relaxed);
+                if pair >= seeds.len() || stop.load(Ordering::Relaxed) {
+                    break;
+                }
+                let seed = seeds[pair];
+                let (outcome, plies) = play(new_game(seed), &mut candidate(), &mut baseline(), config.max_plies);
+                let first = GameRecord { index: 2 * pair, seed, candidate_first: true, outcome, plies };
+                let (outcome, plies) = play(new_game(seed), &mut baseline(), &mut candidate(), config.max_plies);
+                let second = GameRecord { index: 2 * pair + 1, seed, candidate_first: false, outcome: invert(outcome), plies };
+                if sender.send([first, second]).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(sender);
+
+        for pair in receiver {
+            result.games.extend(pair);
+            if matches!(result.sprt_decision(), Some(SprtDecision::AcceptH0 | SprtDecision::AcceptH1)) {
+                stop.store(true, Ordering::Relaxed);
+            }
+        }
+    });
+
+    result.games.sort_by_key(|game| game.index);
+    result
+}
+
+/// Plays one game and returns the outcome for `first` and the number of moves. `first` plays for
+/// the side to move in the start position, so games where a side moves several times in a row
+/// are handled.
+fn play<G: Game>(mut game: G, first: &mut impl Player<G>, second: &mut impl Player<G>, max_plies: u32) -> (Outcome, u32) {
+    let first_side = game.current_player();
+    let mut plies = 0;
+    while plies < max_plies && !game.is_terminal() {
+        let player: &mut dyn Player<G> = if game.current_player() == first_side { first } else { second };
+        let Some(m) = player.play(&game) else {
+            break;
+        };
+        game.make_move(&m);
+        plies += 1;
+    }
+    let evaluation = game.evaluate();
+    let outcome = match evaluation.signum() * if game.current_player() == first_side { 1 } else { -1 } {
+        1 => Outcome::Win,
+        -1 => Outcome::Loss,
+        _ => Outcome::Draw,
+    };
+    (outcome, plies)
+}
+
+fn invert(outcome: Outcome) -> Outcome {
+    match outcome {
+        Outcome::Win => Outcome::Loss,
+        Outcome::Draw => Outcome::Draw,
+        Outcome::Loss => Outcome::Win,
+    }
+}
+
+fn elo_from_score(score: f64) -> f64 {
+    if score <= 0.0 {
+        f64::NEG_INFINITY
+    } else if score >= 1.0 {
+        f64::INFINITY
+    } else {
+        -400.0 * (1.0 / score - 1.0).log10()
+    }
+}
+
+fn score_from_elo(elo: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf(-elo / 400.0))
+}
+
+fn json_number(value: f64) -> String {
+    if value.is_finite() {
+        value.to_string()
+    } else {
+        "null".to_string()
+    }
+}
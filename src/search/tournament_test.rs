@@ -0,0 +1,120 @@
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::random::Random;
+    use crate::search::alpha_beta::{AlphaBeta, SearchConfig};
+    use crate::search::game::Game;
+    use crate::search::test_game::{DoubleMove, TicTacToe};
+    use crate::search::tournament::{
+        run_tournament, GameRecord, Outcome, SprtConfig, SprtDecision, TournamentConfig, TournamentResult,
+    };
+
+    fn perfect() -> AlphaBeta<TicTacToe> {
+        AlphaBeta::new(SearchConfig { max_depth: 9, time_limit: Duration::from_secs(10) })
+    }
+
+    /// Plays random moves, seeded by the position so games are reproducible.
+    fn random_player() -> impl FnMut(&TicTacToe) -> Option<usize> {
+        |game: &TicTacToe| {
+            let moves = game.legal_moves();
+            let mut random = Random::new(game.cells.iter().fold(0, |seed, &cell| seed * 3 + cell as u64));
+            (!moves.is_empty()).then(|| moves[random.below(moves.len())])
+        }
+    }
+
+    fn result(wins: usize, draws: usize, losses: usize, sprt: Option<SprtConfig>) -> TournamentResult {
+        let outcomes = [(Outcome::Win, wins), (Outcome::Draw, draws), (Outcome::Loss, losses)];
+        let games = outcomes
+            .iter()
+            .flat_map(|&(outcome, count)| std::iter::repeat_n(outcome, count))
+            .enumerate()
+            .map(|(index, outcome)| GameRecord { index, seed: 0, candidate_first: index % 2 == 0, outcome, plies: 9 })
+            .collect();
+        TournamentResult { games, sprt }
+    }
+
+    #[test]
+    fn test_perfect_player_never_loses() {
+        let config = TournamentConfig { games: 20, threads: 4, ..Default::default() };
+        let result = run_tournament(&config, |_| TicTacToe::new(), perfect, random_player);
+        assert_eq!(result.games.len(), 20);
+        assert_eq!(result.losses(), 0);
+        assert!(result.wins() > 0);
+        assert!(result.games.iter().enumerate().all(|(index, game)| game.index == index));
+        assert_eq!(result.games[0].seed, result.games[1].seed);
+        assert!(result.games[0].candidate_first && !result.games[1].candidate_first);
+    }
+
+    #[test]
+    fn test_equal_players_draw() {
+        let config = TournamentConfig { games: 3, threads: 2, ..Default::default() };
+        let result = run_tournament(&config, |_| TicTacToe::new(), perfect, perfect);
+        assert_eq!(result.games.len(), 4);
+        assert_eq!(result.draws(), 4);
+        assert_eq!(result.elo(), crate::search::tournament::Elo { difference: 0.0, error: 0.0 });
+    }
+
+    #[test]
+    fn test_same_player_moves_twice() {
+        let perfect = || AlphaBeta::<DoubleMove>::new(SearchConfig { max_depth: 2, time_limit: Duration::from_secs(10) });
+        let config = TournamentConfig { games: 2, threads: 1, ..Default::default() };
+        let result = run_tournament(&config, |_| DoubleMove::default(), perfect, || |_: &DoubleMove| Some(1));
+        // Each side plays both moves of its game: 0, 1 pays 5 and 1, 1 pays 1 for the player moving.
+        assert_eq!(result.games[0].outcome, Outcome::Win);
+        assert_eq!(result.games[1].outcome, Outcome::Loss);
+        assert!(result.games.iter().all(|game| game.plies == 2));
+    }
+
+    #[test]
+    fn test_sprt_stops_early() {
+        let sprt = SprtConfig { elo0: 0.0, elo1: 50.0, ..Default::default() };
+        let config = TournamentConfig { games: 1000, threads: 2, sprt: Some(sprt), ..Default::default() };
+        let result = run_tournament(&config, |_| TicTacToe::new(), perfect, random_player);
+        assert!(result.games.len() < 1000);
+        assert_eq!(result.sprt_decision(), Some(SprtDecision::AcceptH1));
+        // Only whole pairs are played, so both colours got the same number of games.
+        assert_eq!(result.games.len() % 2, 0);
+        assert!(result.games.iter().enumerate().all(|(index, game)| game.index == index));
+        for pair in result.games.chunks(2) {
+            assert_eq!(pair[0].seed, pair[1].seed);
+            assert!(pair[0].candidate_first && !pair[1].candidate_first);
+        }
+    }
+
+    #[test]
+    fn test_elo() {
+        let even = result(10, 0, 10, None);
+        assert_eq!(even.elo().difference, 0.0);
+        assert!(even.elo().error > 100.0);
+
+        let ahead = result(60, 20, 20, None);
+        assert_eq!(ahead.score(), 0.7);
+        assert!((ahead.elo().difference - 147.2).abs() < 0.1);
+        assert!(ahead.elo().error > 30.0 && ahead.elo().error < 100.0);
+
+        assert_eq!(result(5, 0, 0, None).elo().difference, f64::INFINITY);
+    }
+
+    #[test]
+    fn test_sprt_bounds() {
+        let sprt = SprtConfig::default();
+        assert!((sprt.lower_bound() + 2.944).abs() < 0.001);
+        assert!((sprt.upper_bound() - 2.944).abs() < 0.001);
+        assert_eq!(result(3, 4, 3, Some(sprt.clone())).sprt_decision(), Some(SprtDecision::Continue));
+        assert_eq!(result(200, 100, 300, Some(sprt)).sprt_decision(), Some(SprtDecision::AcceptH0));
+        assert_eq!(result(1, 0, 0, None).sprt_decision(), None);
+    }
+
+    #[test]
+    fn test_output() {
+        let result = result(1, 1, 0, None);
+        assert_eq!(result.to_csv(), "game,seed,candidate_first,outcome,plies\n0,0,true,win,9\n1,0,false,draw,9\n");
+        assert_eq!(
+            result.to_json(),
+            "{\"games\":2,\"wins\":1,\"draws\":1,\"losses\":0,\"score\":0.75,\"elo\":190.848501887865,\"eloError\":null,\
+             \"results\":[{\"game\":0,\"seed\":0,\"candidateFirst\":true,\"outcome\":\"win\",\"plies\":9},\
+             {\"game\":1,\"seed\":0,\"candidateFirst\":false,\"outcome\":\"draw\",\"plies\":9}]}"
+        );
+    }
+}
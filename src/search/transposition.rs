@@ -0,0 +1,174 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::mem::size_of;
+use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Bound {
+    /// The score is exact.
+    Exact,
+    /// The search failed high, the real score is at least `score`.
+    Lower,
+    /// The search failed low, the real score is at most `score`.
+    Upper,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TtEntry {
+    pub depth: u8,
+    pub bound: Bound,
+    pub score: i32,
+    /// Fingerprint of the best move, see [`move_key`].
+    pub best_move: Option<u16>,
+}
+
+/// One entry of the table. `check` holds the hash XOR the data, so a torn write from two
+/// threads storing at the same time is detected on probe and the entry treated as missing.
+#[derive(Default)]
+struct Slot {
+    check: AtomicU64,
+    data: AtomicU64,
+}
+
+/// The first slot of a bucket is replaced by deeper or equally deep results and by anything
+/// once its entry is from an older search. The second slot is always replaced.
+#[derive(Default)]
+struct Bucket {
+    slots: [Slot; 2],
+}
+
+/// Transposition table keyed by 64-bit hashes such as the Zobrist hash of a `State`.
+///
+/// All operations take `&self` and are lock-free, so one table can be shared between search
+/// threads through an `Arc`.
+pub struct TranspositionTable {
+    buckets: Vec<Bucket>,
+    age: AtomicU8,
+}
+
+const NO_MOVE: u64 = 0xFFFF;
+
+/// 16-bit fingerprint of a move for [`TtEntry::best_move`]. The move is identified by its value,
+/// so the entry stays valid whatever order `legal_moves()` returns. Never `0xFFFF`.
+pub fn move_key<M: Hash>(m: &M) -> u16 {
+    let mut hasher = DefaultHasher::new();
+    m.hash(&mut hasher);
+    (hasher.finish() as u16).min(0xFFFE)
+}
+
+/// Only 6 bits of the age are stored, so it is compared modulo 64.
+const AGE_MASK: u8 = 0x3F;
+
+impl TranspositionTable {
+    /// Creates a table that uses at most `megabytes` of memory, rounded down to a power of two
+    /// number of buckets.
+    pub fn new(megabytes: usize) -> Self {
+        let budget = (megabytes * 1024 * 1024 / size_of::<Bucket>()).max(1);
+        let count = 1usize << (usize::BITS - 1 - budget.leading_zeros());
+        TranspositionTable { buckets: (0..count).map(|_| Bucket::default()).collect(), age: AtomicU8::new(0) }
+    }
+
+    /// Number of entries the table can hold.
+    pub fn capacity(&self) -> usize {
+        self.buckets.len() * 2
+    }
+
+    pub fn memory_usage(&self) -> usize {
+        self.buckets.len() * size_of::<Bucket>()
+    }
+
+    /// Marks the start of a new search, so that entries of earlier searches are replaced first.
+    pub fn new_search(&self) {
+        self.age.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn clear(&self) {
+        for bucket in &self.buckets {
+            for slot in &bucket.slots {
+                slot.check.store(0, Ordering::Relaxed);
+                slot.data.store(0, Ordering::Relaxed);
+            }
+        }
+    }
+
+    pub fn probe(&self, hash: u64) -> Option<TtEntry> {
+        self.bucket(hash).slots.iter().find_map(|slot| {
+            let data = slot.data.load(Ordering::Relaxed);
+            let check = slot.check.load(Ordering::Relaxed);
+            (data != 0 && check ^ data == hash).then(|| unpack(data))
+        })
+    }
+
+    pub fn store(&self, hash: u64, entry: TtEntry) {
+        let age = self.age();
+        let data = pack(&entry, age);
+        let bucket = self.bucket(hash);
+
+        let first = &bucket.slots[0];
+        let old = first.data.load(Ordering::Relaxed);
+        let same_position = first.check.load(Ordering::Relaxed) ^ old == hash;
+        let replace_first = old == 0 || same_position || entry.depth >= depth_of(old) || age_of(old) != age;
+
+        let slot = if replace_first { first } else { &bucket.slots[1] };
+        slot.check.store(hash ^ data, Ordering::Relaxed);
+        slot.data.store(data, Ordering::Relaxed);
+    }
+
+    /// Permille of sampled entries that were written during the current search.
+    pub fn hashfull(&self) -> usize {
+        let age = self.age();
+        let sample = self.buckets.len().min(500);
+        let used: usize = self.buckets[..sample]
+            .iter()
+            .flat_map(|bucket| bucket.slots.iter())
+            .filter(|slot| {
+                let data = slot.data.load(Ordering::Relaxed);
+                data != 0 && age_of(data) == age
+            })
+            .count();
+        used * 1000 / (sample * 2)
+    }
+
+    fn age(&self) -> u8 {
+        self.age.load(Ordering::Relaxed) & AGE_MASK
+    }
+
+    fn bucket(&self, hash: u64) -> &Bucket {
+        &self.buckets[(hash as usize) & (self.buckets.len() - 1)]
+    }
+}
+
+/// Bits 0-31 score, 32-39 depth, 40-41 bound, 42-47 age, 48-63 best move.
+/// A bound value of 0 is never written, so 0 marks an empty slot.
+fn pack(entry: &TtEntry, age: u8) -> u64 {
+    let bound = match entry.bound {
+        Bound::Exact => 1,
+        Bound::Lower => 2,
+        Bound::Upper => 3,
+    };
+    let best_move = entry.best_move.map_or(NO_MOVE, |m| m.min(0xFFFE) as u64);
+    entry.score as u32 as u64 | (entry.depth as u64) << 32 | bound << 40 | ((age & AGE_MASK) as u64) << 42 | best_move << 48
+}
+
+fn unpack(data: u64) -> TtEntry {
+    let bound = match (data >> 40) & 0b11 {
+        1 => Bound::Exact,
+        2 => Bound::Lower,
+        _ => Bound::Upper,
+    };
+    let best_move = data >> 48;
+    TtEntry {
+        depth: depth_of(data),
+        bound,
+        score: data as u32 as i32,
+        best_move: (best_move != NO_MOVE).then_some(best_move as u16),
+    }
+}
+
+fn depth_of(data: u64) -> u8 {
+    (data >> 32) as u8
+}
+
+fn age_of(data: u64) -> u8 {
+    ((data >> 42) as u8) & AGE_MASK
+}
@@ -0,0 +1,140 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+
+    use crate::search::alpha_beta::{AlphaBeta, SearchConfig};
+    use crate::search::test_game::{TicTacToe, WIN};
+    use crate::search::transposition::{move_key, Bound, TranspositionTable, TtEntry};
+
+    fn entry(depth: u8, score: i32) -> TtEntry {
+        TtEntry { depth, bound: Bound::Exact, score, best_move: Some(3) }
+    }
+
+    #[test]
+    fn test_memory_limit() {
+        let table = TranspositionTable::new(1);
+        assert!(table.memory_usage() <= 1024 * 1024);
+        assert!(table.memory_usage() > 512 * 1024);
+        assert_eq!(table.capacity(), 1024 * 1024 / 16);
+        assert!(TranspositionTable::new(0).capacity() > 0);
+    }
+
+    #[test]
+    fn test_store_and_probe() {
+        let table = TranspositionTable::new(1);
+        assert_eq!(table.probe(42), None);
+
+        let stored = TtEntry { depth: 7, bound: Bound::Upper, score: -12345, best_move: None };
+        table.store(42, stored);
+        assert_eq!(table.probe(42), Some(stored));
+        assert_eq!(table.probe(43), None);
+
+        table.clear();
+        assert_eq!(table.probe(42), None);
+    }
+
+    #[test]
+    fn test_replacement() {
+        let table = TranspositionTable::new(1);
+        let buckets = table.capacity() as u64 / 2;
+        let (a, b, c) = (5, 5 + buckets, 5 + 2 * buckets);
+
+        table.store(a, entry(8, 1));
+        table.store(b, entry(2, 2));
+        // The deep entry is kept, the shallow one goes to the always-replace slot.
+        assert_eq!(table.probe(a), Some(entry(8, 1)));
+        assert_eq!(table.probe(b), Some(entry(2, 2)));
+
+        table.store(c, entry(3, 3));
+        assert_eq!(table.probe(a), Some(entry(8, 1)));
+        assert_eq!(table.probe(b), None);
+        assert_eq!(table.probe(c), Some(entry(3, 3)));
+
+        // Entries of an older search lose their slot to any new result.
+        table.new_search();
+        table.store(b, entry(1, 4));
+        assert_eq!(table.probe(a), None);
+        assert_eq!(table.probe(b), Some(entry(1, 4)));
+    }
+
+    #[test]
+    fn test_hashfull() {
+        let table = TranspositionTable::new(1);
+        assert_eq!(table.hashfull(), 0);
+        for hash in 0..1000 {
+            table.store(hash, entry(1, 0));
+        }
+        assert_eq!(table.hashfull(), 500);
+        table.new_search();
+        assert_eq!(table.hashfull(), 0);
+    }
+
+    #[test]
+    fn test_age_wraps_around() {
+        let table = TranspositionTable::new(1);
+        let buckets = table.capacity() as u64 / 2;
+        for _ in 0..200 {
+            table.new_search();
+        }
+
+        for hash in 0..1000 {
+            table.store(hash, entry(1, 0));
+        }
+        assert_eq!(table.hashfull(), 500);
+
+        // Within the same search the deeper entry still keeps its slot.
+        let (a, b, c) = (5 + buckets * 10, 5 + buckets * 11, 5 + buckets * 12);
+        table.store(a, entry(8, 1));
+        table.store(b, entry(2, 2));
+        table.store(c, entry(3, 3));
+        assert_eq!(table.probe(a), Some(entry(8, 1)));
+        assert_eq!(table.probe(c), Some(entry(3, 3)));
+    }
+
+    #[test]
+    fn test_shared_between_threads() {
+        let table = Arc::new(TranspositionTable::new(1));
+        let handles: Vec<_> = (0..4u64)
+            .map(|thread| {
+                let table = Arc::clone(&table);
+                thread::spawn(move || {
+                    for hash in (thread * 1000)..(thread * 1000 + 1000) {
+                        table.store(hash, entry(1, hash as i32));
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        for hash in 0..4000 {
+            assert_eq!(table.probe(hash).map(|entry| entry.score), Some(hash as i32));
+        }
+    }
+
+    #[test]
+    fn test_move_key() {
+        let keys: Vec<u16> = (0..9usize).map(|m| move_key(&m)).collect();
+        assert_eq!(keys, (0..9usize).map(|m| move_key(&m)).collect::<Vec<_>>());
+        for (i, key) in keys.iter().enumerate() {
+            assert!(!keys[..i].contains(key));
+            assert_ne!(*key, 0xFFFF);
+        }
+    }
+
+    #[test]
+    fn test_search_with_table() {
+        let config = SearchConfig { max_depth: 9, time_limit: Duration::from_secs(10) };
+        let without = AlphaBeta::new(config.clone()).search(&TicTacToe::new());
+        let table = Arc::new(TranspositionTable::new(1));
+        let with = AlphaBeta::new(config.clone()).with_table(table.clone()).search(&TicTacToe::new());
+        assert_eq!(with.score, without.score);
+        assert!(with.nodes < without.nodes);
+
+        let win = AlphaBeta::new(config).with_table(table).search(&TicTacToe::parse("xx.oo...."));
+        assert_eq!(win.best_move, Some(2));
+        assert_eq!(win.score, WIN);
+    }
+}